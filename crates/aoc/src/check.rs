@@ -0,0 +1,242 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::WrapErr;
+use common::input;
+use serde::Deserialize;
+
+use crate::{days, selection::Part, Run};
+
+/// The known answers for a set of puzzle inputs.
+#[derive(Debug, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "answer", default)]
+    answers: Vec<Entry>,
+}
+
+/// The known answers for a single input. Either part can be left out when only one is known.
+#[derive(Debug, Deserialize)]
+struct Entry {
+    day: usize,
+    input: PathBuf,
+    part_1: Option<Expected>,
+    part_2: Option<Expected>,
+}
+
+impl Entry {
+    fn expected(&self, part: Part) -> Option<&Expected> {
+        match part {
+            Part::One => self.part_1.as_ref(),
+            Part::Two => self.part_2.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Expected {
+    Number(u64),
+    Text(String),
+}
+
+impl Expected {
+    fn matches(&self, actual: &str) -> bool {
+        match self {
+            Expected::Number(n) => actual.trim() == n.to_string(),
+            Expected::Text(s) => normalize(s) == normalize(actual),
+        }
+    }
+}
+
+impl std::fmt::Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Number(n) => write!(f, "{}", n),
+            Expected::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Rendered answers (like day 13's letters) are compared without trailing whitespace on each line,
+/// so they can be written naturally in the manifest.
+fn normalize(s: &str) -> Vec<&str> {
+    s.trim_matches('\n').lines().map(str::trim_end).collect()
+}
+
+impl Manifest {
+    /// Loads the manifest at `path`. Inputs in the manifest are relative to the manifest itself.
+    pub fn load(path: &Path) -> color_eyre::Result<Self> {
+        let source = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read manifest {}", path.display()))?;
+        let mut manifest: Manifest = toml::from_str(&source)?;
+
+        let root = path.parent().unwrap_or_else(|| Path::new(""));
+        for entry in manifest.answers.iter_mut() {
+            entry.input = root.join(&entry.input);
+        }
+
+        Ok(manifest)
+    }
+}
+
+/// The outcome of checking one part of one input against the manifest.
+#[derive(Debug)]
+enum Outcome {
+    Pass,
+    Mismatch { expected: String, actual: String },
+    Fail(String),
+}
+
+#[derive(Debug, Default)]
+pub struct Summary {
+    passed: usize,
+    mismatched: usize,
+    failed: usize,
+}
+
+impl Summary {
+    /// `0` when every answer matched, `1` when an answer didn't match, and `2` when a solution
+    /// couldn't produce an answer at all.
+    pub fn exit_code(&self) -> i32 {
+        if self.failed > 0 {
+            2
+        } else if self.mismatched > 0 {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn record(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Pass => self.passed += 1,
+            Outcome::Mismatch { .. } => self.mismatched += 1,
+            Outcome::Fail(_) => self.failed += 1,
+        }
+    }
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} mismatched, {} failed",
+            self.passed, self.mismatched, self.failed
+        )
+    }
+}
+
+/// Checks every manifest entry for the selected days and parts, printing a line per check.
+pub fn check(run: &Run, manifest: &Manifest) -> Summary {
+    let mut summary = Summary::default();
+    let days: Vec<usize> = run.day.days().collect();
+
+    println!("{:>3}  {:>4}  {:<8}  input", "day", "part", "result");
+
+    for entry in manifest.answers.iter().filter(|e| days.contains(&e.day)) {
+        for part in run.parts() {
+            if let Some(expected) = entry.expected(part) {
+                let outcome = check_part(entry, part, expected);
+                summary.record(&outcome);
+                print_outcome(entry, part, &outcome);
+            }
+        }
+    }
+
+    println!("{}", summary);
+
+    summary
+}
+
+fn check_part(entry: &Entry, part: Part, expected: &Expected) -> Outcome {
+    let source = match input::read_to_string(Some(&entry.input)) {
+        Ok(source) => source,
+        Err(e) => return Outcome::Fail(e.to_string()),
+    };
+
+    match solve_quietly(entry.day, &source, part) {
+        Ok(actual) if expected.matches(&actual) => Outcome::Pass,
+        Ok(actual) => Outcome::Mismatch {
+            expected: expected.to_string(),
+            actual,
+        },
+        Err(e) => Outcome::Fail(e),
+    }
+}
+
+/// Solves the part, turning both errors and panics into a message so that one broken day doesn't
+/// stop the rest of the checks from running.
+fn solve_quietly(day: usize, source: &str, part: Part) -> Result<String, String> {
+    let solve = days::solver(day);
+
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| solve(source, part.is_part_2())));
+    panic::set_hook(hook);
+
+    match result {
+        Ok(Ok(solved)) => Ok(solved.answer.to_string()),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(payload) => Err(payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .map(|s| format!("panicked: {}", s))
+            .unwrap_or_else(|| "panicked".to_string())),
+    }
+}
+
+fn print_outcome(entry: &Entry, part: Part, outcome: &Outcome) {
+    let result = match outcome {
+        Outcome::Pass => "pass",
+        Outcome::Mismatch { .. } => "mismatch",
+        Outcome::Fail(_) => "fail",
+    };
+
+    println!(
+        "{:>3}  {:>4}  {:<8}  {}",
+        entry.day,
+        part,
+        result,
+        entry.input.display()
+    );
+
+    match outcome {
+        Outcome::Pass => {}
+        Outcome::Mismatch { expected, actual } => {
+            println!("{:>21}expected: {}", "", expected.trim_end());
+            println!("{:>21}actual:   {}", "", actual.trim_end());
+        }
+        Outcome::Fail(e) => println!("{:>21}{}", "", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{check, Manifest};
+    use crate::{report::Format, selection::Selection, Run};
+
+    #[test]
+    fn test_manifest_answers() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let manifest =
+            Manifest::load(&root.join("answers.toml")).expect("must be able to load the manifest");
+        let run = Run {
+            day: Selection::All,
+            input: None,
+            part: None,
+            inputs: root.join("days"),
+            check: true,
+            manifest: root.join("answers.toml"),
+            format: Format::Table,
+            time: false,
+            repeat: 1,
+        };
+
+        assert_eq!(0, check(&run, &manifest).exit_code());
+    }
+}
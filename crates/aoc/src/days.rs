@@ -0,0 +1,106 @@
+use std::time::{Duration, Instant};
+
+use common::{Answer, Day};
+use rand_chacha::ChaCha8Rng;
+
+/// The signature the runner uses to solve a day. Takes the puzzle input and whether to solve
+/// part 2, and returns the answer along with how long it took.
+pub type Solve = fn(&str, bool) -> color_eyre::Result<Solved>;
+
+/// The answer to one part, and how long parsing and solving took.
+#[derive(Debug)]
+pub struct Solved {
+    pub answer: Answer,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+pub const DAYS: [Solve; 15] = [
+    solve::<day01::Day01>,
+    solve::<day02::Day02>,
+    solve::<day03::Day03>,
+    solve::<day04::Day04>,
+    solve::<day05::Day05>,
+    solve::<day06::Day06>,
+    solve::<day07::Day07>,
+    solve::<day08::Day08>,
+    solve::<day09::Day09>,
+    solve::<day10::Day10>,
+    solve::<day11::Day11>,
+    solve::<day12::Day12>,
+    solve::<day13::Day13>,
+    solve::<day14::Day14>,
+    solve::<day15::Day15>,
+];
+
+pub fn solver(day: usize) -> Solve {
+    DAYS[day - 1]
+}
+
+/// The signature the runner uses to generate a random input for a day, along with the size of
+/// the official input. The meaning of the size differs from day to day.
+pub type Generate = (fn(&mut ChaCha8Rng, usize) -> String, usize);
+
+pub const GENERATORS: [Generate; 15] = [
+    (day01::generate::generate, day01::generate::DEFAULT_SIZE),
+    (day02::generate::generate, day02::generate::DEFAULT_SIZE),
+    (day03::generate::generate, day03::generate::DEFAULT_SIZE),
+    (day04::generate::generate, day04::generate::DEFAULT_SIZE),
+    (day05::generate::generate, day05::generate::DEFAULT_SIZE),
+    (day06::generate::generate, day06::generate::DEFAULT_SIZE),
+    (day07::generate::generate, day07::generate::DEFAULT_SIZE),
+    (day08::generate::generate, day08::generate::DEFAULT_SIZE),
+    (day09::generate::generate, day09::generate::DEFAULT_SIZE),
+    (day10::generate::generate, day10::generate::DEFAULT_SIZE),
+    (day11::generate::generate, day11::generate::DEFAULT_SIZE),
+    (day12::generate::generate, day12::generate::DEFAULT_SIZE),
+    (day13::generate::generate, day13::generate::DEFAULT_SIZE),
+    (day14::generate::generate, day14::generate::DEFAULT_SIZE),
+    (day15::generate::generate, day15::generate::DEFAULT_SIZE),
+];
+
+pub fn generator(day: usize) -> Generate {
+    GENERATORS[day - 1]
+}
+
+fn solve<D: Day>(input: &str, part_2: bool) -> color_eyre::Result<Solved> {
+    let start = Instant::now();
+    let input = D::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = if part_2 {
+        D::part_2(&input)?.into()
+    } else {
+        D::part_1(&input)?.into()
+    };
+    let solve = start.elapsed();
+
+    Ok(Solved {
+        answer,
+        parse,
+        solve,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::{generator, solver};
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for day in 1..=15 {
+            let (generate, size) = generator(day);
+            let input = generate(&mut ChaCha8Rng::seed_from_u64(0), size);
+
+            for part_2 in [false, true] {
+                if let Err(e) = solver(day)(&input, part_2) {
+                    panic!("day {} failed on a generated input: {}", day, e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+use clap::Parser;
+use color_eyre::eyre::eyre;
+use common::input;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use report::{Format, Report, Row};
+use selection::{Part, Selection};
+use timing::{Phases, Timings};
+
+mod check;
+mod days;
+mod report;
+mod selection;
+mod timing;
+
+#[derive(Debug, Parser)]
+/// Runs the solutions to Advent of Code 2021.
+enum Options {
+    /// Solves a single day, or every day with `all`.
+    Run(Run),
+    /// Writes a random puzzle input for a day to stdout.
+    Generate(Generate),
+}
+
+#[derive(Debug, Parser)]
+struct Run {
+    #[clap()]
+    /// The day to solve (1 to 15), or `all` to solve every day.
+    day: Selection,
+
+    #[clap()]
+    /// Path to the file that contains the input, or `-` for stdin. Defaults to the day's
+    /// `input/input.txt`.
+    input: Option<PathBuf>,
+
+    #[clap(long)]
+    /// Only solve the given part (1 or 2). Both parts are solved by default.
+    part: Option<Part>,
+
+    #[clap(long, default_value = "days")]
+    /// The directory holding each day's crate, used to find default inputs.
+    inputs: PathBuf,
+
+    #[clap(long)]
+    /// Compare the answers for every input in the manifest against their known answers instead.
+    /// Exits with 1 if an answer didn't match, or 2 if a solution failed.
+    check: bool,
+
+    #[clap(long, default_value = "answers.toml")]
+    /// The manifest of known answers used by `--check`.
+    manifest: PathBuf,
+
+    #[clap(long, default_value = "table")]
+    /// How to print the answers: `table`, or `json` for one JSON object per line.
+    format: Format,
+
+    #[clap(long)]
+    /// Show how long reading, parsing and solving took for each part.
+    time: bool,
+
+    #[clap(long, default_value = "1")]
+    /// Solve each part this many times and report the min, median and max time. Implies `--time`.
+    repeat: usize,
+}
+
+#[derive(Debug, Parser)]
+struct Generate {
+    #[clap()]
+    /// The day to generate an input for (1 to 15).
+    day: Selection,
+
+    #[clap(long, default_value = "0")]
+    /// The seed for the random number generator. The same seed always generates the same input.
+    seed: u64,
+
+    #[clap(long)]
+    /// How big an input to generate, such as the number of lines or the width of a grid depending
+    /// on the day. Defaults to the size of the official input.
+    size: Option<usize>,
+}
+
+impl Run {
+    fn parts(&self) -> Vec<Part> {
+        self.part
+            .map(|p| vec![p])
+            .unwrap_or_else(|| Part::BOTH.to_vec())
+    }
+
+    fn input_for(&self, day: usize) -> color_eyre::Result<PathBuf> {
+        match (&self.input, self.day) {
+            (Some(_), Selection::All) => {
+                Err(eyre!("an input file can only be passed for a single day"))
+            }
+            (Some(input), Selection::Day(_)) => Ok(input.clone()),
+            (None, _) => Ok(default_input(&self.inputs, day)),
+        }
+    }
+
+    fn report(&self) -> Report {
+        Report {
+            format: self.format,
+            timed: self.time || self.repeat > 1,
+        }
+    }
+}
+
+fn default_input(inputs: &Path, day: usize) -> PathBuf {
+    inputs
+        .join(format!("day{:02}", day))
+        .join("input")
+        .join("input.txt")
+}
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    match Options::parse() {
+        Options::Run(run) if run.check => check_days(&run),
+        Options::Run(run) => run_days(&run),
+        Options::Generate(generate) => generate_input(&generate),
+    }
+}
+
+fn generate_input(options: &Generate) -> color_eyre::Result<()> {
+    let day = match options.day {
+        Selection::Day(day) => day,
+        Selection::All => return Err(eyre!("inputs can only be generated for a single day")),
+    };
+
+    let (generate, default_size) = days::generator(day);
+    let mut rng = ChaCha8Rng::seed_from_u64(options.seed);
+
+    print!(
+        "{}",
+        generate(&mut rng, options.size.unwrap_or(default_size))
+    );
+
+    Ok(())
+}
+
+fn check_days(run: &Run) -> color_eyre::Result<()> {
+    if run.input.is_some() {
+        return Err(eyre!("`--check` takes its inputs from the manifest"));
+    }
+
+    let manifest = check::Manifest::load(&run.manifest)?;
+    let summary = check::check(run, &manifest);
+
+    std::process::exit(summary.exit_code())
+}
+
+fn run_days(run: &Run) -> color_eyre::Result<()> {
+    if run.repeat == 0 {
+        return Err(eyre!("`--repeat` must be at least 1"));
+    }
+
+    let report = run.report();
+    let mut failures = 0;
+
+    report.print_header();
+
+    for day in run.day.days() {
+        let path = run.input_for(day)?;
+        if run.repeat > 1 && path.as_os_str() == input::STDIN {
+            return Err(eyre!(
+                "`--repeat` needs an input file, stdin can only be read once"
+            ));
+        }
+
+        let solve = days::solver(day);
+
+        // the input is read once before any of the parts are solved, since stdin can only be read
+        // once. Every part reuses that read, unless `--repeat` asks for more runs, in which case
+        // the runs after the first read the file again to time their own reads
+        let start = Instant::now();
+        let day_input = input::read_to_string(Some(&path))?;
+        let day_read = start.elapsed();
+        let mut first_run = true;
+
+        for part in run.parts() {
+            let mut timings = Timings::default();
+            let mut input_hash = 0;
+            let mut answer = Err(eyre!("not solved"));
+
+            for _ in 0..run.repeat {
+                let (input, read) = if first_run || run.repeat == 1 {
+                    first_run = false;
+                    (Cow::Borrowed(day_input.as_str()), day_read)
+                } else {
+                    let start = Instant::now();
+                    let input = input::read_to_string(Some(&path))?;
+                    (Cow::Owned(input), start.elapsed())
+                };
+
+                input_hash = report::input_hash(&input);
+
+                match solve(&input, part.is_part_2()) {
+                    Ok(solved) => {
+                        timings.push(Phases {
+                            read,
+                            parse: solved.parse,
+                            solve: solved.solve,
+                        });
+                        answer = Ok(solved.answer);
+                    }
+                    Err(e) => {
+                        // a failure won't go away by trying again
+                        answer = Err(e);
+                        break;
+                    }
+                }
+            }
+
+            if answer.is_err() {
+                failures += 1;
+            }
+
+            report.print_row(&Row {
+                day,
+                part,
+                answer: &answer,
+                timings: &timings,
+                input_hash,
+            });
+        }
+    }
+
+    if failures > 0 {
+        return Err(eyre!("{} solution(s) failed", failures));
+    }
+
+    Ok(())
+}
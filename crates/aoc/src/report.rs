@@ -0,0 +1,183 @@
+use std::{str::FromStr, time::Duration};
+
+use common::Answer;
+use serde_json::json;
+use thiserror::Error;
+
+use crate::{
+    selection::Part,
+    timing::{Stats, Timings},
+};
+
+/// How the runner prints its results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// An aligned table meant for people.
+    Table,
+    /// One JSON object per line meant for scripts. Every object has the same fields:
+    ///
+    /// - `day` and `part` as numbers
+    /// - `answer` as a number, or a string for text answers like day 13's letters, and `null` if
+    ///   the solution failed
+    /// - `error` with the failure message, or `null` if the solution succeeded
+    /// - `elapsed_ns`, the median time spent parsing and solving in nanoseconds
+    /// - `input_hash`, the 64-bit FNV-1a hash of the input as 16 hex digits
+    /// - `runs`, how many times the part was solved
+    /// - `read_ns`, `parse_ns`, `solve_ns` and `total_ns`, each an object with the `min`, `median`
+    ///   and `max` time of that phase in nanoseconds
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            other => Err(UnknownFormat(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("unknown format `{0}`, expected `table` or `json`")]
+pub struct UnknownFormat(String);
+
+/// The result of solving one part of one day.
+pub struct Row<'a> {
+    pub day: usize,
+    pub part: Part,
+    pub answer: &'a color_eyre::Result<Answer>,
+    pub timings: &'a Timings,
+    pub input_hash: u64,
+}
+
+/// Prints results in a format, optionally with the time each phase took.
+#[derive(Debug, Clone, Copy)]
+pub struct Report {
+    pub format: Format,
+    pub timed: bool,
+}
+
+impl Report {
+    pub fn print_header(&self) {
+        match self.format {
+            Format::Table if self.timed => println!(
+                "{:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}  answer",
+                "day", "part", "read", "parse", "solve", "min", "median", "max"
+            ),
+            Format::Table => println!("{:>3}  {:>4}  answer", "day", "part"),
+            Format::Json => {}
+        }
+    }
+
+    pub fn print_row(&self, row: &Row) {
+        match self.format {
+            Format::Table => self.print_table_row(row),
+            Format::Json => print_json_row(row),
+        }
+    }
+
+    fn print_table_row(&self, row: &Row) {
+        let answer = match row.answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) => format!("error: {}", e),
+        };
+        let mut lines = answer.lines();
+
+        // every row starts with the same columns, which continuation lines leave blank
+        let (columns, blank) = if self.timed {
+            let total = row.timings.total();
+            let columns = format!(
+                "{:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}",
+                row.day,
+                row.part,
+                millis(row.timings.read().median),
+                millis(row.timings.parse().median),
+                millis(row.timings.solve().median),
+                millis(total.min),
+                millis(total.median),
+                millis(total.max),
+            );
+            let width = columns.len();
+
+            (columns, " ".repeat(width))
+        } else {
+            (format!("{:>3}  {:>4}", row.day, row.part), " ".repeat(9))
+        };
+
+        println!("{}  {}", columns, lines.next().unwrap_or_default());
+
+        // answers that span several lines (like day 13's rendered letters) continue under the answer column
+        for line in lines {
+            println!("{}  {}", blank, line);
+        }
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn print_json_row(row: &Row) {
+    let (answer, error) = match row.answer {
+        Ok(Answer::Number(n)) => (json!(n), json!(null)),
+        Ok(Answer::Text(s)) => (json!(s), json!(null)),
+        Err(e) => (json!(null), json!(e.to_string())),
+    };
+
+    let object = json!({
+        "day": row.day,
+        "part": row.part.number(),
+        "answer": answer,
+        "error": error,
+        "elapsed_ns": nanos(row.timings.parse_and_solve().median),
+        "input_hash": format!("{:016x}", row.input_hash),
+        "runs": row.timings.runs(),
+        "read_ns": stats_json(row.timings.read()),
+        "parse_ns": stats_json(row.timings.parse()),
+        "solve_ns": stats_json(row.timings.solve()),
+        "total_ns": stats_json(row.timings.total()),
+    });
+
+    println!("{}", object);
+}
+
+fn stats_json(stats: Stats) -> serde_json::Value {
+    json!({
+        "min": nanos(stats.min),
+        "median": nanos(stats.median),
+        "max": nanos(stats.max),
+    })
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+/// Hashes the input with 64-bit FNV-1a, which is simple enough to reproduce anywhere that wants
+/// to match results to inputs.
+pub fn input_hash(input: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for &byte in input.as_bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        // reference values for 64-bit FNV-1a
+        assert_eq!(input_hash(""), 0xcbf29ce484222325);
+        assert_eq!(input_hash("a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(input_hash("foobar"), 0x85944171f73967e8);
+    }
+}
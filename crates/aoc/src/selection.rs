@@ -0,0 +1,87 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::days::DAYS;
+
+/// Which days the runner should solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    Day(usize),
+}
+
+impl Selection {
+    pub fn days(&self) -> impl Iterator<Item = usize> {
+        match *self {
+            Selection::All => 1..=DAYS.len(),
+            Selection::Day(day) => day..=day,
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = SelectionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Selection::All),
+            s => match s.parse() {
+                Ok(day) if (1..=DAYS.len()).contains(&day) => Ok(Selection::Day(day)),
+                _ => Err(SelectionParseError::UnknownDay(s.to_string())),
+            },
+        }
+    }
+}
+
+/// Which half of a day's puzzle to solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn is_part_2(&self) -> bool {
+        matches!(self, Part::Two)
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = SelectionParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            other => Err(SelectionParseError::UnknownPart(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum SelectionParseError {
+    #[error("{0} is not a day that has a solution. Expected `all` or a number from 1 to 15")]
+    UnknownDay(String),
+
+    #[error("{0} is not a part of the puzzle. Expected 1 or 2")]
+    UnknownPart(String),
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+/// How long each phase of solving one part took on a single run.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Phases {
+    /// Reading the input file.
+    pub read: Duration,
+    /// Parsing the input into the day's model.
+    pub parse: Duration,
+    /// Solving the part from the parsed model.
+    pub solve: Duration,
+}
+
+impl Phases {
+    pub fn total(&self) -> Duration {
+        self.read + self.parse + self.solve
+    }
+}
+
+/// The spread of a duration over repeated runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes the samples. With an even number of samples the median is the upper of the two
+    /// middle samples, so that it's always a duration that was actually measured.
+    pub fn of(samples: impl Iterator<Item = Duration>) -> Stats {
+        let mut samples: Vec<_> = samples.collect();
+        samples.sort_unstable();
+
+        Stats {
+            min: samples.first().copied().unwrap_or_default(),
+            median: samples.get(samples.len() / 2).copied().unwrap_or_default(),
+            max: samples.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+/// The phases of every run of one part.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    runs: Vec<Phases>,
+}
+
+impl Timings {
+    pub fn push(&mut self, phases: Phases) {
+        self.runs.push(phases);
+    }
+
+    pub fn runs(&self) -> usize {
+        self.runs.len()
+    }
+
+    pub fn read(&self) -> Stats {
+        Stats::of(self.runs.iter().map(|p| p.read))
+    }
+
+    pub fn parse(&self) -> Stats {
+        Stats::of(self.runs.iter().map(|p| p.parse))
+    }
+
+    pub fn solve(&self) -> Stats {
+        Stats::of(self.runs.iter().map(|p| p.solve))
+    }
+
+    /// Parsing and solving together, leaving out reading the input.
+    pub fn parse_and_solve(&self) -> Stats {
+        Stats::of(self.runs.iter().map(|p| p.parse + p.solve))
+    }
+
+    pub fn total(&self) -> Stats {
+        Stats::of(self.runs.iter().map(|p| p.total()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+
+        let odd = Stats::of([ms(3), ms(1), ms(2)].into_iter());
+        assert_eq!(
+            odd,
+            Stats {
+                min: ms(1),
+                median: ms(2),
+                max: ms(3)
+            }
+        );
+
+        let even = Stats::of([ms(4), ms(1), ms(3), ms(2)].into_iter());
+        assert_eq!(even.median, ms(3));
+
+        assert_eq!(Stats::of(std::iter::empty()).max, Duration::ZERO);
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt::Display;
+
+use nom::error::{ErrorKind, VerboseError, VerboseErrorKind};
+
+/// A problem with the puzzle input. Points at the line and column where things went wrong and
+/// says what was expected there instead, for example:
+///
+/// ```text
+/// line 3, column 5: expected `->` between points
+///   |
+/// 3 | 0,9 => 5,9
+///   |     ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The line the problem is on, counting from 1.
+    pub line: usize,
+    /// The column the problem starts at, counting characters from 1.
+    pub column: usize,
+    /// The text of the offending line.
+    pub source: String,
+    /// What was expected at that point, like "`->` between points".
+    pub expected: String,
+}
+
+impl Diagnostic {
+    pub fn new(
+        line: usize,
+        column: usize,
+        source: impl Into<String>,
+        expected: impl Into<String>,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            source: source.into(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Points at the byte `offset` into `input`.
+    pub fn at_offset(input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self::new(
+            input[..start].matches('\n').count() + 1,
+            input[start..offset].chars().count() + 1,
+            input[start..end].trim_end_matches('\r'),
+            expected,
+        )
+    }
+
+    /// Points at the start of `rest`, which is what was left of `input` when parsing failed.
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        Self::at_offset(input, input.len() - rest.len(), expected)
+    }
+
+    /// Converts an error from a nom parser run over `input`. The diagnostic points at where the
+    /// innermost parser failed, and explains it with the innermost `context` wrapped around it.
+    pub fn from_nom(input: &str, error: VerboseError<&str>) -> Self {
+        let rest = error.errors.first().map_or("", |(rest, _)| rest);
+        let expected = error
+            .errors
+            .iter()
+            .find_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(context.to_string()),
+                _ => None,
+            })
+            .or_else(|| {
+                error.errors.first().map(|(_, kind)| match kind {
+                    VerboseErrorKind::Char(c) => format!("`{}`", c),
+                    VerboseErrorKind::Nom(kind) => describe(*kind),
+                    VerboseErrorKind::Context(context) => context.to_string(),
+                })
+            })
+            .unwrap_or_else(|| "valid input".to_string());
+
+        Self::at(input, rest, expected)
+    }
+
+    /// Moves the diagnostic down by `lines`, for when the input that was parsed started partway
+    /// through the puzzle input.
+    pub fn below(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+/// Describes what a failing nom parser was looking for.
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "a number",
+        ErrorKind::Alpha => "letters",
+        ErrorKind::AlphaNumeric => "letters or numbers",
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace",
+        ErrorKind::CrLf => "a line ending",
+        ErrorKind::Eof => "the end of the input",
+        ErrorKind::MapRes => "a value in range",
+        other => return format!("{:?}", other).to_lowercase(),
+    }
+    .to_string()
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = self.line.to_string().len();
+
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "{:gutter$} |", "", gutter = gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source)?;
+        write!(
+            f,
+            "{:gutter$} | {:>column$}",
+            "",
+            "^",
+            gutter = gutter,
+            column = self.column
+        )
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag, character::complete::digit1, error::context, sequence::tuple, Finish,
+        IResult,
+    };
+
+    use super::Diagnostic;
+
+    #[test]
+    fn test_display() {
+        let input = "0,9 -> 5,9\n8,0 -> 0,8\n0,9 => 5,9\n";
+        let diagnostic = Diagnostic::at_offset(input, 26, "`->` between points");
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "line 3, column 5: expected `->` between points\n  |\n3 | 0,9 => 5,9\n  |     ^"
+        );
+    }
+
+    #[test]
+    fn test_from_nom() {
+        fn arrow(input: &str) -> IResult<&str, (&str, &str, &str), nom::error::VerboseError<&str>> {
+            tuple((digit1, context("`->` between points", tag(" -> ")), digit1))(input)
+        }
+
+        let input = "1 => 2";
+        let error = arrow(input).finish().unwrap_err();
+
+        assert_eq!(
+            Diagnostic::from_nom(input, error),
+            Diagnostic::new(1, 2, "1 => 2", "`->` between points")
+        );
+    }
+}
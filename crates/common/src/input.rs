@@ -0,0 +1,24 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::Path,
+};
+
+/// The path which stands in for stdin on the command line.
+pub const STDIN: &str = "-";
+
+/// Opens the puzzle input at `path` for buffered reading. Passing no path at all, or `-`, reads
+/// the input from stdin instead, so inputs can be piped in from other tools.
+pub fn open(path: Option<&Path>) -> io::Result<Box<dyn BufRead>> {
+    match path {
+        Some(path) if path != Path::new(STDIN) => Ok(Box::new(BufReader::new(File::open(path)?))),
+        _ => Ok(Box::new(BufReader::new(io::stdin()))),
+    }
+}
+
+/// Reads the entire puzzle input at `path` into memory. See [open] for how the path is treated.
+pub fn read_to_string(path: Option<&Path>) -> io::Result<String> {
+    let mut buf = String::new();
+    open(path)?.read_to_string(&mut buf)?;
+    Ok(buf)
+}
@@ -0,0 +1,32 @@
+//! Pieces shared by every day's solution.
+pub use answer::Answer;
+pub use diagnostic::Diagnostic;
+
+mod answer;
+mod diagnostic;
+pub mod input;
+
+/// A single day of Advent of Code. Each day parses its puzzle input into a typed model once and
+/// then solves both parts of the puzzle from that model.
+pub trait Day {
+    /// The day of the advent calendar this is the solution for.
+    const DAY: usize;
+
+    /// The model the puzzle input is parsed into.
+    type Input;
+
+    /// The answer to part 1 of the puzzle.
+    type Part1: Into<Answer>;
+
+    /// The answer to part 2 of the puzzle.
+    type Part2: Into<Answer>;
+
+    /// Parses the puzzle input into the model both parts are solved from.
+    fn parse(input: &str) -> color_eyre::Result<Self::Input>;
+
+    /// Solves part 1 of the puzzle.
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1>;
+
+    /// Solves part 2 of the puzzle.
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2>;
+}
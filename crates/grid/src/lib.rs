@@ -0,0 +1,301 @@
+//! A rectangular 2D grid shared by the days whose puzzle input is a map of cells.
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use common::Diagnostic;
+
+pub use point::{Neighborhood, Neighbors, Point};
+
+mod point;
+
+/// A rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid out of `cells` laid out row by row, each row `width` cells long.
+    ///
+    /// Panics if the cells don't fill a whole number of rows.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells can't be split into rows {} cells wide",
+            cells.len(),
+            width
+        );
+
+        Self { width, cells }
+    }
+
+    /// Parses a grid with one row per line, turning each character into a cell with `cell`.
+    /// `expected` describes a valid cell for when `cell` rejects a character.
+    ///
+    /// Every row has to be as long as the first one, and there has to be at least one row.
+    pub fn parse_with<F>(input: &str, expected: &str, mut cell: F) -> Result<Self, Diagnostic>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (i, line) in input.trim_end().lines().enumerate() {
+            let line = line.trim_end_matches('\r');
+            let mut count = 0;
+
+            for (column, c) in line.char_indices() {
+                let next = cell(c)
+                    .ok_or_else(|| Diagnostic::at_offset(line, column, expected).below(i))?;
+                cells.push(next);
+                count += 1;
+            }
+
+            match width {
+                None if count == 0 => break,
+                None => width = Some(count),
+                Some(width) if width != count => {
+                    let column = line
+                        .char_indices()
+                        .nth(width)
+                        .map_or(line.len(), |(c, _)| c);
+                    let expected = format!("a row {} cells long, like the first", width);
+                    return Err(Diagnostic::at_offset(line, column, expected).below(i));
+                }
+                Some(_) => {}
+            }
+        }
+
+        let width = width.ok_or_else(|| Diagnostic::at_offset(input, 0, "a row of cells"))?;
+        Ok(Self { width, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    /// The number of cells in the grid.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The point just past the bottom right corner, so `width` by `height`.
+    pub fn bounds(&self) -> Point {
+        Point::new(self.width(), self.height())
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width() && point.y < self.height()
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[self.index_of(point)])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.contains(point)
+            .then(|| self.index_of(point))
+            .map(move |index| &mut self.cells[index])
+    }
+
+    /// Where `point` is in [Grid::cells].
+    pub fn index_of(&self, point: Point) -> usize {
+        point.y * self.width + point.x
+    }
+
+    /// Which point the cell at `index` in [Grid::cells] is at.
+    pub fn point_of(&self, index: usize) -> Point {
+        Point::new(index % self.width, index / self.width)
+    }
+
+    /// Every cell, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Every point in the grid, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.len()).map(|i| self.point_of(i))
+    }
+
+    /// Every point in the grid along with its cell, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.points().zip(&self.cells)
+    }
+
+    /// The neighbours of `point` which are inside the grid.
+    pub fn neighbors(&self, point: Point, neighborhood: Neighborhood) -> Neighbors {
+        point.neighbors(self.width(), self.height(), neighborhood)
+    }
+
+    /// Each row from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks_exact(self.width)
+    }
+
+    /// The cells in column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Each column from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a grid of single digits, like the height maps and risk levels in a few of the
+    /// puzzles.
+    pub fn parse_digits(input: &str) -> Result<Self, Diagnostic> {
+        Self::parse_with(input, "a digit from 0 to 9", |c| {
+            c.to_digit(10).map(|d| d as u8)
+        })
+    }
+}
+
+impl FromStr for Grid<u8> {
+    type Err = Diagnostic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_digits(s)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        assert!(self.contains(point), "{:?} is outside of the grid", point);
+        &self.cells[self.index_of(point)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        assert!(self.contains(point), "{:?} is outside of the grid", point);
+        let index = self.index_of(point);
+        &mut self.cells[index]
+    }
+}
+
+/// Writes each row on its own line, with the cells next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, Neighborhood, Point};
+
+    const EXAMPLE: &str = "2199943210\n3987894921\n9856789892\n8767896789\n9899965678\n";
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid: Grid<u8> = EXAMPLE.parse().unwrap();
+
+        assert_eq!((10, 5), (grid.width(), grid.height()));
+        assert_eq!(Some(&5), grid.get(Point::new(2, 2)));
+        assert_eq!(8, grid[Point::new(1, 2)]);
+        assert_eq!(None, grid.get(Point::new(10, 0)));
+        assert_eq!(EXAMPLE, grid.to_string());
+    }
+
+    #[test]
+    fn test_far_out_of_bounds() {
+        let mut grid: Grid<u8> = EXAMPLE.parse().unwrap();
+        let far = Point::new(0, usize::MAX);
+
+        assert_eq!(None, grid.get(far));
+        assert_eq!(None, grid.get_mut(far));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Grid::parse_digits("123\n1x3\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "a digit from 0 to 9");
+
+        let error = Grid::parse_digits("123\n1234\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+
+        let error = Grid::parse_digits("123\n12\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+
+        assert!(Grid::parse_digits("\n").is_err());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::from_cells(3, vec![0; 9]);
+        let neighbors = |x, y, n| {
+            grid.neighbors(Point::new(x, y), n)
+                .map(|p| grid.index_of(p))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec![0, 1, 2, 3, 5, 6, 7, 8],
+            neighbors(1, 1, Neighborhood::Eight)
+        );
+        assert_eq!(vec![1, 3, 4], neighbors(0, 0, Neighborhood::Eight));
+        assert_eq!(vec![4, 5, 7], neighbors(2, 2, Neighborhood::Eight));
+        assert_eq!(vec![1, 2, 4, 7, 8], neighbors(2, 1, Neighborhood::Eight));
+        assert_eq!(vec![0, 1, 4, 6, 7], neighbors(0, 1, Neighborhood::Eight));
+
+        assert_eq!(vec![1, 3, 5, 7], neighbors(1, 1, Neighborhood::Four));
+        assert_eq!(vec![1, 3], neighbors(0, 0, Neighborhood::Four));
+        assert_eq!(vec![5, 7], neighbors(2, 2, Neighborhood::Four));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::from_cells(3, (0..6).collect());
+
+        assert_eq!(
+            vec![&[0, 1, 2][..], &[3, 4, 5]],
+            grid.rows().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![vec![0, 3], vec![1, 4], vec![2, 5]],
+            grid.columns()
+                .map(|c| c.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Point::new(2, 1), grid.point_of(5));
+    }
+}
@@ -0,0 +1,99 @@
+/// A cell in a grid. `x` counts columns from the left and `y` counts rows from the top.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const ZERO: Self = Self { x: 0, y: 0 };
+
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// The [Manhattan distance](https://en.wikipedia.org/wiki/Taxicab_geometry) between two points.
+    pub fn distance(&self, other: &Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The neighbours of this point which are inside a `width` by `height` grid.
+    pub fn neighbors(self, width: usize, height: usize, neighborhood: Neighborhood) -> Neighbors {
+        Neighbors {
+            center: self,
+            width,
+            height,
+            offsets: neighborhood.offsets().iter(),
+        }
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Which cells count as being next to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// Only the cells above, below, left and right, sharing an edge.
+    Four,
+
+    /// The cells sharing an edge along with the four diagonals, sharing a corner.
+    Eight,
+}
+
+impl Neighborhood {
+    /// The offsets of each neighbour, in reading order.
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Self::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+/// An iterator over the neighbours of a point, in reading order. Neighbours which would fall
+/// outside of the grid are skipped. Created by [Point::neighbors].
+#[derive(Debug, Clone)]
+pub struct Neighbors {
+    center: Point,
+    width: usize,
+    height: usize,
+    offsets: std::slice::Iter<'static, (isize, isize)>,
+}
+
+impl Iterator for Neighbors {
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for &(dx, dy) in self.offsets.by_ref() {
+            let x = self
+                .center
+                .x
+                .checked_add_signed(dx)
+                .filter(|&x| x < self.width);
+            let y = self
+                .center
+                .y
+                .checked_add_signed(dy)
+                .filter(|&y| y < self.height);
+
+            if let Some((x, y)) = x.zip(y) {
+                return Some(Point { x, y });
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,308 @@
+//! More ways of looking at the sonar sweep than counting how often the depth increases. Each
+//! analysis is a [Solver], so it can sit behind the same buffers and windows as [State], and a
+//! [Pipeline] runs several of them over the same depths so the input is only read once.
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{Solver, State};
+
+/// Counts how many times the depth decreases. The mirror image of [State].
+#[derive(Debug, Default)]
+pub struct Decreases(Option<(usize, i64)>);
+
+impl Solver for Decreases {
+    fn next(&mut self, num: i64) {
+        self.0 = Some(match self.0 {
+            Some((sum, prev)) if num < prev => (sum + 1, num),
+            Some((sum, _)) => (sum, num),
+            None => (0, num),
+        });
+    }
+
+    fn solution(&self) -> usize {
+        self.0.map_or(0, |(sum, _)| sum)
+    }
+}
+
+/// Finds the longest run of strictly rising depths, counted in depths. A lone depth is a run of 1.
+#[derive(Debug, Default)]
+pub struct LongestRise {
+    /// The longest run so far
+    longest: usize,
+
+    /// The length of the current run, along with the last depth in it
+    current: Option<(usize, i64)>,
+}
+
+impl Solver for LongestRise {
+    fn next(&mut self, num: i64) {
+        let length = match self.current {
+            Some((length, prev)) if num > prev => length + 1,
+            _ => 1,
+        };
+
+        self.current = Some((length, num));
+        self.longest = self.longest.max(length);
+    }
+
+    fn solution(&self) -> usize {
+        self.longest
+    }
+}
+
+/// Finds the largest drop in depth from one reading to the next. If the depth never drops that's 0.
+#[derive(Debug, Default)]
+pub struct LargestDrop {
+    largest: usize,
+    prev: Option<i64>,
+}
+
+impl Solver for LargestDrop {
+    fn next(&mut self, num: i64) {
+        if let Some(prev) = self.prev {
+            // widen before subtracting, since the drop between two far apart i64s won't fit in one
+            let drop = i128::from(prev) - i128::from(num);
+            if drop > 0 {
+                self.largest = self.largest.max(drop as usize);
+            }
+        }
+
+        self.prev = Some(num);
+    }
+
+    fn solution(&self) -> usize {
+        self.largest
+    }
+}
+
+/// Counts the plateaus, where the depth stays the same for two or more readings in a row. However
+/// long a plateau is it only counts once.
+#[derive(Debug, Default)]
+pub struct Plateaus {
+    count: usize,
+
+    /// The last depth, and whether it's already part of a plateau
+    prev: Option<(i64, bool)>,
+}
+
+impl Solver for Plateaus {
+    fn next(&mut self, num: i64) {
+        let on_plateau = match self.prev {
+            Some((prev, on_plateau)) if prev == num => {
+                if !on_plateau {
+                    self.count += 1;
+                }
+                true
+            }
+            _ => false,
+        };
+
+        self.prev = Some((num, on_plateau));
+    }
+
+    fn solution(&self) -> usize {
+        self.count
+    }
+}
+
+/// Records where the depth increased, as the index of each reading which was deeper than the one
+/// before it. Behind a window these are the indices of the window sums instead. The solution is
+/// the number of increases, just like [State], and the report lists where they were.
+#[derive(Debug, Default)]
+pub struct IncreaseEvents {
+    events: Vec<usize>,
+    seen: usize,
+    prev: Option<i64>,
+}
+
+impl IncreaseEvents {
+    pub fn events(&self) -> &[usize] {
+        &self.events
+    }
+}
+
+impl Solver for IncreaseEvents {
+    fn next(&mut self, num: i64) {
+        if self.prev.is_some_and(|prev| num > prev) {
+            self.events.push(self.seen);
+        }
+
+        self.prev = Some(num);
+        self.seen += 1;
+    }
+
+    fn solution(&self) -> usize {
+        self.events.len()
+    }
+
+    fn report(&self) -> String {
+        let events: Vec<String> = self.events.iter().map(usize::to_string).collect();
+        events.join(",")
+    }
+}
+
+/// Each of the analyses which can be picked on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Analysis {
+    Increases,
+    Decreases,
+    LongestRise,
+    LargestDrop,
+    Plateaus,
+    IncreaseEvents,
+}
+
+impl Analysis {
+    pub const ALL: [Self; 6] = [
+        Self::Increases,
+        Self::Decreases,
+        Self::LongestRise,
+        Self::LargestDrop,
+        Self::Plateaus,
+        Self::IncreaseEvents,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Increases => "increases",
+            Self::Decreases => "decreases",
+            Self::LongestRise => "longest-rise",
+            Self::LargestDrop => "largest-drop",
+            Self::Plateaus => "plateaus",
+            Self::IncreaseEvents => "increase-events",
+        }
+    }
+
+    /// A fresh solver for this analysis.
+    pub fn solver(self) -> Box<dyn Solver> {
+        match self {
+            Self::Increases => Box::new(State::default()),
+            Self::Decreases => Box::new(Decreases::default()),
+            Self::LongestRise => Box::new(LongestRise::default()),
+            Self::LargestDrop => Box::new(LargestDrop::default()),
+            Self::Plateaus => Box::new(Plateaus::default()),
+            Self::IncreaseEvents => Box::new(IncreaseEvents::default()),
+        }
+    }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Analysis {
+    type Err = UnknownAnalysis;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|a| a.name() == s)
+            .ok_or_else(|| UnknownAnalysis(s.to_string()))
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("{0} is not an analysis. Expected one of increases, decreases, longest-rise, largest-drop, plateaus or increase-events")]
+pub struct UnknownAnalysis(String);
+
+/// Feeds every depth into several analyses at once.
+pub struct Pipeline {
+    stages: Vec<(Analysis, Box<dyn Solver>)>,
+}
+
+impl Pipeline {
+    pub fn new(analyses: &[Analysis]) -> Self {
+        Self {
+            stages: analyses.iter().map(|&a| (a, a.solver())).collect(),
+        }
+    }
+
+    /// The report from each analysis, in the order they were given.
+    pub fn results(&self) -> impl Iterator<Item = (Analysis, String)> + '_ {
+        self.stages.iter().map(|(a, s)| (*a, s.report()))
+    }
+}
+
+impl Solver for Pipeline {
+    fn next(&mut self, num: i64) {
+        for (_, solver) in &mut self.stages {
+            solver.next(num);
+        }
+    }
+
+    /// The solution of the first analysis, so a pipeline of one analysis solves just like it.
+    fn solution(&self) -> usize {
+        self.stages.first().map_or(0, |(_, s)| s.solution())
+    }
+
+    /// Every analysis's report on its own line, labelled with its name.
+    fn report(&self) -> String {
+        let lines: Vec<String> = self
+            .results()
+            .map(|(analysis, report)| format!("{}: {}", analysis, report))
+            .collect();
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Analysis, Pipeline};
+    use crate::{chain, feed, Solver};
+
+    const EXAMPLE: [i32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    fn solve(analysis: Analysis, depths: &[i32]) -> String {
+        let mut solver = analysis.solver();
+        for &depth in depths {
+            solver.next(depth.into());
+        }
+        solver.report()
+    }
+
+    #[test]
+    fn test_analyses() {
+        assert_eq!("7", solve(Analysis::Increases, &EXAMPLE));
+        assert_eq!("2", solve(Analysis::Decreases, &EXAMPLE));
+        assert_eq!("4", solve(Analysis::LongestRise, &EXAMPLE));
+        assert_eq!("10", solve(Analysis::LargestDrop, &EXAMPLE));
+        assert_eq!("0", solve(Analysis::Plateaus, &EXAMPLE));
+        assert_eq!("1,2,3,5,6,7,9", solve(Analysis::IncreaseEvents, &EXAMPLE));
+
+        assert_eq!("2", solve(Analysis::Plateaus, &[1, 1, 1, 2, 3, 3, 4]));
+        assert_eq!("0", solve(Analysis::LongestRise, &[]));
+        assert_eq!(
+            "4294967295",
+            solve(Analysis::LargestDrop, &[i32::MAX, i32::MIN])
+        );
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let pipeline = Pipeline::new(&[Analysis::Decreases, Analysis::IncreaseEvents]);
+        let solver = chain(&[3], Box::new(pipeline));
+
+        assert_eq!(1, feed(solver, &EXAMPLE));
+
+        let mut solver = chain(&[3], Box::new(Pipeline::new(&Analysis::ALL)));
+        for depth in EXAMPLE {
+            solver.next(depth.into());
+        }
+
+        assert_eq!(
+            "increases: 5\ndecreases: 1\nlongest-rise: 5\nlargest-drop: 1\nplateaus: 1\nincrease-events: 1,4,5,6,7",
+            solver.report()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        for analysis in Analysis::ALL {
+            assert_eq!(analysis, analysis.name().parse().unwrap());
+        }
+        assert!("sideways".parse::<Analysis>().is_err());
+    }
+}
@@ -0,0 +1,132 @@
+//! Following a depth log which is still being written to, like `tail -f`. Rather than stopping at
+//! the end of the input, a [Follow] keeps its solver around and feeds it each new line as it shows
+//! up, so the answer can be reported as it changes.
+use std::{cell::RefCell, io::BufRead, rc::Rc};
+
+use crate::{depth, Solver};
+
+/// Feeds lines into a solver as they're appended to the input.
+pub struct Follow<S: Solver, R: BufRead> {
+    solver: S,
+    reader: R,
+
+    /// Whatever has been read of a line which hasn't been finished yet
+    partial: String,
+
+    /// How many lines have been read so far, for pointing at bad lines
+    lines: usize,
+}
+
+impl<S: Solver, R: BufRead> Follow<S, R> {
+    pub fn new(solver: S, reader: R) -> Self {
+        Self {
+            solver,
+            reader,
+            partial: String::new(),
+            lines: 0,
+        }
+    }
+
+    /// Reads every complete line which is available right now into the solver, returning how many
+    /// there were. A line which is still being written (so doesn't end in a newline yet) is held on
+    /// to until the rest of it arrives.
+    pub fn poll(&mut self) -> color_eyre::Result<usize> {
+        let mut read = 0;
+
+        while self.reader.read_line(&mut self.partial)? > 0 {
+            if !self.partial.ends_with('\n') {
+                // we've caught up with the writer partway through a line
+                break;
+            }
+
+            self.solver.next(depth(&self.partial, self.lines)?.into());
+            self.partial.clear();
+            self.lines += 1;
+            read += 1;
+        }
+
+        Ok(read)
+    }
+
+    pub fn solver(&self) -> &S {
+        &self.solver
+    }
+}
+
+/// Passes everything it's fed on to the next solver, keeping a copy on the side which can be taken
+/// through [Tap::seen]. At the end of a chain of windows this catches the window sums, which is how
+/// follow mode shows them as they come in.
+pub struct Tap<I: Solver> {
+    seen: Rc<RefCell<Vec<i64>>>,
+    next: I,
+}
+
+impl<I: Solver> Tap<I> {
+    pub fn new(next: I) -> Self {
+        Self {
+            seen: Rc::default(),
+            next,
+        }
+    }
+
+    /// A handle onto the numbers this tap has seen. It stays usable after the tap has been boxed up
+    /// into a chain, and the numbers can be taken out of it as they're dealt with.
+    pub fn seen(&self) -> Rc<RefCell<Vec<i64>>> {
+        Rc::clone(&self.seen)
+    }
+}
+
+impl<I: Solver> Solver for Tap<I> {
+    fn next(&mut self, num: i64) {
+        self.seen.borrow_mut().push(num);
+        self.next.next(num);
+    }
+
+    fn solution(&self) -> usize {
+        self.next.solution()
+    }
+
+    fn report(&self) -> String {
+        self.next.report()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, Write};
+
+    use super::{Follow, Tap};
+    use crate::{chain, Solver, State};
+
+    #[test]
+    fn test_follow_growing_file() {
+        let path = std::env::temp_dir().join(format!("day01-follow-{}.txt", std::process::id()));
+        let mut writer = std::fs::File::create(&path).unwrap();
+        let reader = BufReader::new(std::fs::File::open(&path).unwrap());
+
+        let tap = Tap::new(State::default());
+        let sums = tap.seen();
+        let mut follow = Follow::new(chain(&[3], Box::new(tap)), reader);
+        assert_eq!(0, follow.poll().unwrap());
+
+        write!(writer, "199\n200\n208\n21").unwrap();
+        assert_eq!(3, follow.poll().unwrap());
+        assert_eq!(0, follow.solver().solution());
+        assert_eq!(vec![607], sums.take());
+
+        write!(writer, "0\n200\n207\n").unwrap();
+        assert_eq!(3, follow.poll().unwrap());
+        assert_eq!(1, follow.solver().solution());
+        assert_eq!(vec![618, 618, 617], sums.take());
+
+        assert_eq!(0, follow.poll().unwrap());
+        writeln!(writer, "240\n269\n260\n263").unwrap();
+        assert_eq!(4, follow.poll().unwrap());
+        assert_eq!(5, follow.solver().solution());
+
+        writeln!(writer, "deeper").unwrap();
+        assert!(follow.poll().is_err());
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,17 @@
+use rand::Rng;
+
+/// How many depths the official input has.
+pub const DEFAULT_SIZE: usize = 2000;
+
+/// Generates `size` depths that drift deeper with some noise, like a real sonar sweep.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut depth: i32 = rng.gen_range(100..200);
+
+    (0..size)
+        .map(|_| {
+            let line = format!("{}\n", depth);
+            depth = (depth + rng.gen_range(-10..=15)).max(0);
+            line
+        })
+        .collect()
+}
@@ -0,0 +1,477 @@
+//! Code for [AOC day 1](https://adventofcode.com/2021/day/1).
+use std::io::BufRead;
+
+use common::{Day, Diagnostic};
+
+pub mod analysis;
+pub mod follow;
+pub mod generate;
+pub mod parallel;
+
+/// This trait is for a type which can be used to solve today's problem.
+pub trait Solver {
+    /// This method is for feeding a particular input digit into the type.
+    /// Today's problem has a long list of numbers (here represented as 32 bit signed integers (i32))
+    /// which are fed into the computation one by one. This method is for feeding the next int
+    /// into the solver. The ints are widened to i64 on the way in, since windows pass their sums
+    /// on through here too, and the sum of a big window of depths won't fit in an i32.
+    fn next(&mut self, num: i64);
+
+    /// Once all of the ints have been fed into the solver. Calling this method will return the solution.
+    /// Since we're counting, we use an unsigned int.
+    fn solution(&self) -> usize;
+
+    /// Some solvers find out more than a single count, like where in the input things happened.
+    /// This method returns everything the solver found as text for printing. By default that's
+    /// just the solution.
+    fn report(&self) -> String {
+        self.solution().to_string()
+    }
+}
+
+/// This type holds the current state of the computation. Before we've seen any values
+/// The internal [Option] will be None. Once we've seen input we're able to set the state
+/// to a tuple where the first value is the current count of rising depths and the second
+/// number is the previous depth. This process is modeled as a state machine.
+///
+/// Alongside that we also remember the very first depth we saw. Counting doesn't need it, but it's
+/// what lets two states which saw neighbouring chunks of the input be joined together (see [Merge]).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    /// The state machine described above
+    current: Option<(usize, i64)>,
+
+    /// The first depth this state saw, if it has seen any
+    first: Option<i64>,
+}
+
+impl State {
+    /// Given a number, produce the next State.
+    fn transition(&self, num: i64) -> Self {
+        // Rust's match statement is super convenient for modeling state transitions.
+        let current = match self.current {
+            // if we haven't seen any input yet, then we initialize the state to Some((0, whatever the first number is)).
+            None => Some((0, num)),
+
+            // If we have seen input and the last number is smaller than the current number, then we increase the sum by one
+            // and store the current number.
+            Some((sum, prev)) if num > prev => Some((sum + 1, num)),
+
+            // If the number isn't greater than the previous number, then we don't increment the sum and we just store
+            // the current number.
+            Some((sum, _)) => Some((sum, num)),
+        };
+
+        State {
+            current,
+            // the first number only ever gets set once
+            first: self.first.or(Some(num)),
+        }
+    }
+}
+
+/// Implementation of solver for our state
+impl Solver for State {
+    fn next(&mut self, num: i64) {
+        // Given new input, we set ourselves to whatever the next state is.
+        *self = self.transition(num);
+    }
+
+    fn solution(&self) -> usize {
+        // the solution is whatever the sum is at the given moment, or 0 if we haven't seen any input
+        self.current.map(|(s, _)| s).unwrap_or(0)
+    }
+}
+
+/// Part 2 of this problem asks that we buffer up numbers into groups of 3. This type implements a buffer
+/// which is able to be placed in front of a downstream solver. This buffer is parameterized by the downstream
+/// solver and also the size of the buffer, which is passed at compile time as a const usize.
+#[derive(Debug)]
+pub struct Buffer<I: Solver, const S: usize> {
+    /// The buffer itself. This is an array which is instantiated inline into this type. Since this type
+    /// will be on the stack this buffer doesn't require any allocation.
+    window: [i64; S],
+
+    /// The first numbers this buffer saw, kept so buffers can be merged (see [Merge]).
+    head: [i64; S],
+
+    /// The count of inputs this buffer has seen
+    count: usize,
+
+    /// The next solver in the chain
+    next: I,
+}
+
+impl<I: Solver, const S: usize> Buffer<I, S> {
+    /// Constructs a new buffer which is placed in front of some downstream solver.
+    pub fn new(next: I) -> Self {
+        Self {
+            window: [0; S],
+            head: [0; S],
+            count: 0,
+            next,
+        }
+    }
+
+    /// Pushes new input into the buffer. Returns `Some(i64)` if the buffer is full
+    /// and the sum of the buffer is able to be reported. Otherwise returns `None`.
+    /// Where `S == 3`, this method will return `None` until 3 input numbers have
+    /// been pushed.
+    pub fn push(&mut self, num: i64) -> Option<i64> {
+        // since order inside the buffer doesn't matter we can just write to it like a
+        // [ring buffer](https://en.wikipedia.org/wiki/Circular_buffer)
+        self.window[self.count % S] = num;
+        if let Some(slot) = self.head.get_mut(self.count) {
+            *slot = num;
+        }
+        self.count += 1;
+        (self.count >= S).then(|| self.window.iter().sum())
+    }
+
+    /// The last `n` numbers this buffer saw (or fewer, if it hasn't seen that many), oldest first.
+    /// `n` can't be more than `S`, since older numbers have been overwritten.
+    fn recent(&self, n: usize) -> impl Iterator<Item = i64> + '_ {
+        (1..=n.min(self.count))
+            .rev()
+            .map(move |back| self.window[(self.count - back) % S])
+    }
+
+    /// The first `S` numbers this buffer saw, or fewer if it hasn't seen that many.
+    fn earliest(&self) -> &[i64] {
+        &self.head[..self.count.min(S)]
+    }
+}
+
+impl<I: Solver + Default, const S: usize> Default for Buffer<I, S> {
+    fn default() -> Self {
+        Self::new(I::default())
+    }
+}
+
+impl<I: Solver, const S: usize> Solver for Buffer<I, S> {
+    fn next(&mut self, num: i64) {
+        // if there is a sum ready from the buffer then send it to the downstream Solver.
+        if let Some(sum) = self.push(num) {
+            self.next.next(sum);
+        }
+    }
+
+    fn solution(&self) -> usize {
+        // The buffer's solution is whatever the downstream solution is.
+        self.next.solution()
+    }
+
+    fn report(&self) -> String {
+        // and the same goes for the report
+        self.next.report()
+    }
+}
+
+/// Solvers which can be run over separate chunks of the input and joined back together afterwards.
+/// This is what allows a huge input to be split up and solved in parallel.
+pub trait Merge: Solver + Sized {
+    /// Joins this solver with `next`, a solver which saw the chunk of input right after this
+    /// solver's chunk. The result is the same as if one solver had seen both chunks in order.
+    ///
+    /// Merging has to be associative, so that it doesn't matter which neighbouring chunks get joined
+    /// first, and a fresh solver (one which hasn't seen anything) has to leave the other solver
+    /// unchanged when merged.
+    fn merge(self, next: Self) -> Self;
+}
+
+impl Merge for State {
+    fn merge(self, next: Self) -> Self {
+        match (self.current, next.current, next.first) {
+            // the only place an increase can hide is at the seam between the two chunks, where the
+            // first depth of the next chunk is compared against the last depth of this one.
+            (Some((sum, last)), Some((next_sum, next_last)), Some(next_first)) => State {
+                current: Some((sum + next_sum + usize::from(next_first > last), next_last)),
+                first: self.first,
+            },
+
+            // if either side hasn't seen anything then there's nothing to join
+            (None, _, _) => next,
+            _ => self,
+        }
+    }
+}
+
+impl<I: Merge + Default, const S: usize> Merge for Buffer<I, S> {
+    fn merge(self, next: Self) -> Self {
+        // Neither buffer could produce the sums of the windows which straddle the seam between the
+        // two chunks, since each only saw its own side. Those windows are all made up of the last
+        // few numbers of this chunk followed by the first few numbers of the next.
+        let seam: Vec<i64> = self
+            .recent(S - 1)
+            .chain(next.earliest().iter().take(S - 1).copied())
+            .collect();
+
+        // the straddling sums come after every sum from this chunk and before every sum from the
+        // next, so they get their own downstream solver which is merged in between the two.
+        let mut straddling = I::default();
+        for window in seam.windows(S) {
+            straddling.next(window.iter().sum());
+        }
+
+        let count = self.count + next.count;
+
+        // the merged buffer holds the last numbers of both chunks together, laid out just like it
+        // would be if the numbers had been pushed one by one
+        let mut window = [0; S];
+        let recent: Vec<i64> = self.recent(S).chain(next.recent(S)).collect();
+        for (back, &num) in recent.iter().rev().take(S).enumerate() {
+            window[(count - 1 - back) % S] = num;
+        }
+
+        // and the first numbers of both chunks together
+        let mut head = [0; S];
+        for (slot, &num) in head
+            .iter_mut()
+            .zip(self.earliest().iter().chain(next.earliest()))
+        {
+            *slot = num;
+        }
+
+        Self {
+            window,
+            head,
+            count,
+            next: self.next.merge(straddling).merge(next.next),
+        }
+    }
+}
+
+/// Like [Buffer], but the size of the window is picked at runtime rather than at compile time. The
+/// window has to live on the heap because of that, so rather than summing the whole window every
+/// time we keep a running sum and just swap the oldest number out for the newest.
+#[derive(Debug)]
+pub struct Window<I: Solver> {
+    /// The buffer itself, used as a ring buffer just like [Buffer]'s.
+    window: Vec<i64>,
+
+    /// The count of inputs this window has seen
+    count: usize,
+
+    /// The sum of everything currently in the window
+    sum: i64,
+
+    /// The next solver in the chain
+    next: I,
+}
+
+impl<I: Solver> Window<I> {
+    /// Constructs a new window of `size` numbers which is placed in front of some downstream solver.
+    /// Panics if `size` is 0, since an empty window would never have a sum to pass on.
+    pub fn new(size: usize, next: I) -> Self {
+        assert!(size > 0, "a window must hold at least one number");
+
+        Self {
+            window: vec![0; size],
+            count: 0,
+            sum: 0,
+            next,
+        }
+    }
+
+    /// Pushes new input into the window. Works just like [Buffer::push].
+    pub fn push(&mut self, num: i64) -> Option<i64> {
+        // the slot we're about to overwrite holds either the oldest number in the window, or 0 if
+        // the window hasn't filled up yet, so either way it can come straight off the sum.
+        let size = self.window.len();
+        let slot = &mut self.window[self.count % size];
+        self.sum += num - *slot;
+        *slot = num;
+        self.count += 1;
+        (self.count >= size).then_some(self.sum)
+    }
+}
+
+impl<I: Solver> Solver for Window<I> {
+    fn next(&mut self, num: i64) {
+        if let Some(sum) = self.push(num) {
+            self.next.next(sum);
+        }
+    }
+
+    fn solution(&self) -> usize {
+        self.next.solution()
+    }
+
+    fn report(&self) -> String {
+        self.next.report()
+    }
+}
+
+/// A boxed solver is a solver too. This lets chains of buffers and windows be put together at
+/// runtime, see [windowed].
+impl<S: Solver + ?Sized> Solver for Box<S> {
+    fn next(&mut self, num: i64) {
+        (**self).next(num)
+    }
+
+    fn solution(&self) -> usize {
+        (**self).solution()
+    }
+
+    fn report(&self) -> String {
+        (**self).report()
+    }
+}
+
+/// The largest window [windowed] will build as a [Buffer]. Anything larger becomes a [Window].
+pub const MAX_BUFFER_SIZE: usize = 8;
+
+/// Places a window of `size` numbers in front of `next`. Since the window size is only known at
+/// runtime we can't name a `Buffer<_, size>` directly, but for small sizes we can still match on
+/// the size and pick the const generic [Buffer] which fits, which keeps the window on the stack.
+/// Panics if `size` is 0.
+pub fn windowed(size: usize, next: Box<dyn Solver>) -> Box<dyn Solver> {
+    match size {
+        1 => Box::new(Buffer::<_, 1>::new(next)),
+        2 => Box::new(Buffer::<_, 2>::new(next)),
+        3 => Box::new(Buffer::<_, 3>::new(next)),
+        4 => Box::new(Buffer::<_, 4>::new(next)),
+        5 => Box::new(Buffer::<_, 5>::new(next)),
+        6 => Box::new(Buffer::<_, 6>::new(next)),
+        7 => Box::new(Buffer::<_, 7>::new(next)),
+        MAX_BUFFER_SIZE => Box::new(Buffer::<_, MAX_BUFFER_SIZE>::new(next)),
+        _ => Box::new(Window::new(size, next)),
+    }
+}
+
+/// Chains a window for each of `sizes` in front of `solver`. The depths go into the first window,
+/// its sums go into the second window and so on, with the sums from the last window going into
+/// `solver`. So `chain(&[3, 5], solver)` sums every 5 consecutive sums of 3 depths.
+pub fn chain(sizes: &[usize], solver: Box<dyn Solver>) -> Box<dyn Solver> {
+    sizes
+        .iter()
+        .rev()
+        .fold(solver, |next, &size| windowed(size, next))
+}
+
+/// The `run` function takes a solver and input (in the form of some type which implements BufRead)
+/// and reads each line of the input into the solver. It then reports the solution if it succeeds.
+pub fn run<S: Solver, B: BufRead>(mut solver: S, reader: B) -> color_eyre::Result<usize> {
+    read(&mut solver, reader)?;
+
+    // return the solution once there are no more remaining lines.
+    Ok(solver.solution())
+}
+
+/// Like `run`, but leaves the solver with the caller afterwards, for when there's more to get out
+/// of it than the solution.
+pub fn read<S: Solver + ?Sized, B: BufRead>(solver: &mut S, reader: B) -> color_eyre::Result<()> {
+    // for each line of the input
+    // Note: this is the only place in the application where there is any heap allocation. We could
+    // potentially optimize that by reusing a buffer, but why?
+    for (number, line) in reader.lines().enumerate() {
+        // parse the line (bubbling errors up along the way if anything fails), and then pass the
+        // parsed number into the solver.
+        solver.next(depth(&line?, number)?.into());
+    }
+
+    Ok(())
+}
+
+/// Parses a single depth from the `line` with the given index, pointing at it if it isn't a number.
+fn depth(line: &str, number: usize) -> Result<i32, Diagnostic> {
+    line.trim().parse().map_err(|_| {
+        let column = line.len() - line.trim_start().len();
+        Diagnostic::at_offset(line, column, "a depth as a whole number").below(number)
+    })
+}
+
+/// Today's solution for the workspace wide [Day] trait.
+pub struct Day01;
+
+impl Day for Day01 {
+    const DAY: usize = 1;
+
+    type Input = Vec<i32>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        // unlike `run` we parse everything up front, so the same depths can be fed into either part
+        Ok(input
+            .lines()
+            .enumerate()
+            .map(|(number, line)| depth(line, number))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        Ok(feed(State::default(), input))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        Ok(feed(Buffer::<State, 3>::new(State::default()), input))
+    }
+}
+
+/// Like `run`, but for depths which have already been parsed.
+fn feed<S: Solver>(mut solver: S, depths: &[i32]) -> usize {
+    for &depth in depths {
+        solver.next(depth.into());
+    }
+
+    solver.solution()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chain, feed, Buffer, Solver, State, Window};
+
+    const EXAMPLE: [i32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_window_matches_buffer() {
+        let depths: Vec<i32> = (0..200).map(|i| (i * 7919) % 101 - 50).collect();
+
+        assert_eq!(
+            feed(Buffer::<State, 3>::new(State::default()), &depths),
+            feed(Window::new(3, State::default()), &depths)
+        );
+        assert_eq!(
+            feed(
+                Buffer::<Buffer<State, 5>, 3>::new(Buffer::new(State::default())),
+                &depths
+            ),
+            feed(chain(&[3, 5], Box::new(State::default())), &depths)
+        );
+        assert_eq!(
+            feed(Window::new(20, Window::new(3, State::default())), &depths),
+            feed(chain(&[20, 3], Box::new(State::default())), &depths)
+        );
+    }
+
+    #[test]
+    fn test_sums_past_i32() {
+        // the sums of two deep readings don't fit in an i32, but they still have to compare right
+        let depths = [0, i32::MAX, i32::MAX, i32::MAX, i32::MAX - 1];
+        assert_eq!(1, feed(Buffer::<State, 2>::default(), &depths));
+        assert_eq!(1, feed(Window::new(2, State::default()), &depths));
+
+        // and chained windows add sums of sums
+        let depths: Vec<i32> = std::iter::once(0).chain([i32::MAX; 10]).collect();
+        assert_eq!(1, feed(chain(&[9, 2], Box::new(State::default())), &depths));
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(7, feed(chain(&[], Box::new(State::default())), &EXAMPLE));
+        assert_eq!(7, feed(chain(&[1], Box::new(State::default())), &EXAMPLE));
+        assert_eq!(5, feed(chain(&[3], Box::new(State::default())), &EXAMPLE));
+        assert_eq!(5, feed(Window::new(3, State::default()), &EXAMPLE));
+    }
+
+    #[test]
+    fn test_boxed_solver() {
+        let mut solver: Box<dyn Solver> = Box::new(State::default());
+        for depth in EXAMPLE {
+            solver.next(depth.into());
+        }
+
+        assert_eq!(7, solver.solution());
+    }
+}
@@ -0,0 +1,192 @@
+//! Solving very large depth logs on several threads at once. The input is split into chunks, each
+//! chunk is folded by its own solver, and the solvers are then merged back together in order (see
+//! [Merge]) so the answer is exactly what [run](crate::run) would have given.
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use common::Diagnostic;
+use rayon::prelude::*;
+
+use crate::{read, Merge};
+
+/// Solves the depths in the file at `path` by splitting the file into `chunks` pieces of about
+/// the same size and solving each piece on its own thread. Each thread reads its own piece straight
+/// from the file, so the file never has to fit in memory.
+pub fn run_parallel<S>(path: &Path, chunks: usize) -> color_eyre::Result<usize>
+where
+    S: Merge + Default + Send,
+{
+    let bounds = chunk_bounds(path, chunks)?;
+
+    let solver = bounds
+        .par_windows(2)
+        .map(|range| {
+            let (start, end) = (range[0], range[1]);
+            let mut file = File::open(path)?;
+            file.seek(SeekFrom::Start(start))?;
+
+            let mut solver = S::default();
+            if let Err(error) = read(&mut solver, BufReader::new(file.take(end - start))) {
+                return Err(match error.downcast::<Diagnostic>() {
+                    // the chunk counts its lines from its own start, so they're moved down past
+                    // the lines before it to point at the right place in the file
+                    Ok(diagnostic) => diagnostic.below(lines_before(path, start)?).into(),
+                    Err(error) => {
+                        error.wrap_err(format!("in the chunk starting at byte {}", start))
+                    }
+                });
+            }
+            Ok::<_, color_eyre::Report>(solver)
+        })
+        .try_reduce(S::default, |l, r| Ok(l.merge(r)))?;
+
+    Ok(solver.solution())
+}
+
+/// Like [run_parallel], but for depths which have already been parsed. Each chunk is
+/// `chunk_size` depths long.
+pub fn feed_parallel<S>(depths: &[i32], chunk_size: usize) -> usize
+where
+    S: Merge + Default + Send,
+{
+    depths
+        .par_chunks(chunk_size.max(1))
+        .map(|chunk| {
+            let mut solver = S::default();
+            for &depth in chunk {
+                solver.next(depth.into());
+            }
+            solver
+        })
+        .reduce(S::default, S::merge)
+        .solution()
+}
+
+/// Finds where each chunk of the file starts, along with where the file ends. The file is split
+/// into `chunks` even pieces and then each split is pushed forwards to the start of the next line,
+/// so no line is ever split across two chunks.
+fn chunk_bounds(path: &Path, chunks: usize) -> color_eyre::Result<Vec<u64>> {
+    let len = std::fs::metadata(path)?.len();
+    let chunks = chunks.max(1) as u64;
+
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut skipped = Vec::new();
+    let mut bounds = vec![0];
+
+    for i in 1..chunks {
+        let split = len * i / chunks;
+
+        // start from the byte before the split, so a split which already sits at the start of a
+        // line stays where it is
+        reader.seek(SeekFrom::Start(split.saturating_sub(1)))?;
+        skipped.clear();
+        let bound = split.saturating_sub(1) + reader.read_until(b'\n', &mut skipped)? as u64;
+
+        if bound > *bounds.last().expect("bounds starts off with 0") && bound < len {
+            bounds.push(bound);
+        }
+    }
+
+    bounds.push(len);
+    Ok(bounds)
+}
+
+/// How many lines there are in the file before `offset`, which has to be the start of a line. This
+/// is only needed when a chunk fails, so it doesn't mind reading everything up to there.
+fn lines_before(path: &Path, offset: u64) -> io::Result<usize> {
+    let mut reader = BufReader::new(File::open(path)?.take(offset));
+    let mut line = Vec::new();
+    let mut lines = 0;
+
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        line.clear();
+        lines += 1;
+    }
+
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use common::Diagnostic;
+
+    use super::{feed_parallel, run_parallel};
+    use crate::{feed, Buffer, State};
+
+    fn depths() -> Vec<i32> {
+        (0..1000i32)
+            .map(|i| (i * 7919 + i * i * 31) % 211 - 100)
+            .collect()
+    }
+
+    #[test]
+    fn test_feed_parallel_matches_serial() {
+        let depths = depths();
+        let part_1 = feed(State::default(), &depths);
+        let part_2 = feed(Buffer::<State, 3>::default(), &depths);
+        let window_7 = feed(Buffer::<Buffer<State, 2>, 7>::default(), &depths);
+
+        for chunk_size in [1, 2, 3, 4, 5, 7, 10, 64, 999, 1000, 5000] {
+            assert_eq!(part_1, feed_parallel::<State>(&depths, chunk_size));
+            assert_eq!(
+                part_2,
+                feed_parallel::<Buffer<State, 3>>(&depths, chunk_size)
+            );
+            assert_eq!(
+                window_7,
+                feed_parallel::<Buffer<Buffer<State, 2>, 7>>(&depths, chunk_size)
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_parallel_matches_serial() {
+        let depths = depths();
+        let path = std::env::temp_dir().join(format!("day01-parallel-{}.txt", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        for depth in &depths {
+            writeln!(file, "{}", depth).unwrap();
+        }
+        drop(file);
+
+        for chunks in [1, 2, 3, 8, 100, 5000] {
+            assert_eq!(
+                feed(State::default(), &depths),
+                run_parallel::<State>(&path, chunks).unwrap()
+            );
+            assert_eq!(
+                feed(Buffer::<State, 3>::default(), &depths),
+                run_parallel::<Buffer<State, 3>>(&path, chunks).unwrap()
+            );
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_error_line_in_later_chunk() {
+        let path = std::env::temp_dir().join(format!("day01-bad-line-{}.txt", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        for (i, depth) in depths().iter().enumerate() {
+            if i == 899 {
+                writeln!(file, "deeper").unwrap();
+            } else {
+                writeln!(file, "{}", depth).unwrap();
+            }
+        }
+        drop(file);
+
+        for chunks in [1, 4, 100] {
+            let error = run_parallel::<State>(&path, chunks).unwrap_err();
+            let diagnostic = error.downcast::<Diagnostic>().unwrap();
+            assert_eq!((900, 1), (diagnostic.line, diagnostic.column));
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,50 +1,126 @@
-use std::{num::ParseIntError, str::FromStr};
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
 
+use common::Diagnostic;
 use thiserror::Error;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Command {
     Forward(i32),
+    Back(i32),
     Up(i32),
     Down(i32),
+
+    /// Sets the aim outright. Only [Part2](crate::Part2) keeps track of aim, so
+    /// [Part1](crate::Part1) ignores this command.
+    SetAim(i32),
 }
 
-struct CommandParser<'a, I: Iterator<Item = &'a str>> {
-    iter: I,
+impl Command {
+    /// The number the command was given.
+    pub fn amount(self) -> i32 {
+        match self {
+            Self::Forward(x) | Self::Back(x) | Self::Up(x) | Self::Down(x) | Self::SetAim(x) => x,
+        }
+    }
 }
 
-impl<'a, I: Iterator<Item = &'a str>> CommandParser<'a, I> {
-    fn new(iter: I) -> Self {
-        Self { iter }
+impl Display for Command {
+    /// Writes the command the way it would appear in a script.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Forward(x) => write!(f, "forward {}", x),
+            Self::Back(x) => write!(f, "back {}", x),
+            Self::Up(x) => write!(f, "up {}", x),
+            Self::Down(x) => write!(f, "down {}", x),
+            Self::SetAim(x) => write!(f, "set aim {}", x),
+        }
     }
+}
+
+/// A word from a line, along with the byte offset on the line where it starts.
+pub(crate) type Token<'a> = (usize, &'a str);
+
+/// An error along with the byte offset on the line where it happened.
+pub(crate) type Located = (usize, CommandParseError);
 
-    fn next(&mut self) -> Result<&'a str, CommandParseError> {
-        self.iter.next().ok_or(CommandParseError::IncompleteCommand)
+/// Splits `line` up into words, remembering where each of them starts.
+pub(crate) fn tokenize(line: &str) -> Vec<Token<'_>> {
+    line.split_whitespace()
+        .map(|word| (word.as_ptr() as usize - line.as_ptr() as usize, word))
+        .collect()
+}
+
+struct CommandParser<'a, 't> {
+    tokens: std::slice::Iter<'t, Token<'a>>,
+
+    /// Where the line ends, which is where anything missing from the end of it should have been
+    end: usize,
+}
+
+impl<'a, 't> CommandParser<'a, 't> {
+    fn new(tokens: &'t [Token<'a>], end: usize) -> Self {
+        Self {
+            tokens: tokens.iter(),
+            end,
+        }
+    }
+
+    fn next(&mut self) -> Result<Token<'a>, Located> {
+        self.tokens
+            .next()
+            .copied()
+            .ok_or((self.end, CommandParseError::IncompleteCommand))
     }
 
-    fn parse_amount(&mut self) -> Result<i32, CommandParseError> {
-        self.next()?.parse().map_err(CommandParseError::BadInteger)
+    fn parse_amount(&mut self) -> Result<i32, Located> {
+        let (at, word) = self.next()?;
+        word.parse()
+            .map_err(|e| (at, CommandParseError::BadInteger(e)))
     }
 
-    fn parse(&mut self) -> Result<Command, CommandParseError> {
+    fn parse(&mut self) -> Result<Command, Located> {
         let cmd = match self.next()? {
-            "forward" => Command::Forward,
-            "up" => Command::Up,
-            "down" => Command::Down,
-            unknown => return Err(CommandParseError::UnknownVerb(unknown.into())),
+            (_, "forward") => Command::Forward,
+            (_, "back") => Command::Back,
+            (_, "up") => Command::Up,
+            (_, "down") => Command::Down,
+            (_, "set") => match self.next()? {
+                (_, "aim") => Command::SetAim,
+                (at, unknown) => {
+                    return Err((at, CommandParseError::UnknownSetting(unknown.into())))
+                }
+            },
+            (at, unknown) => return Err((at, CommandParseError::UnknownVerb(unknown.into()))),
         };
 
-        self.parse_amount().map(cmd)
+        let cmd = self.parse_amount().map(cmd)?;
+
+        match self.tokens.next() {
+            Some(&(at, extra)) => Err((at, CommandParseError::TrailingInput(extra.into()))),
+            None => Ok(cmd),
+        }
     }
 }
 
+/// Parses a single command out of the words of a line which ends at `end`.
+pub(crate) fn parse_tokens(tokens: &[Token], end: usize) -> Result<Command, Located> {
+    CommandParser::new(tokens, end).parse()
+}
+
 impl FromStr for Command {
     type Err = CommandParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        CommandParser::new(s.split_whitespace()).parse()
+        parse_tokens(&tokenize(s), s.trim_end().len()).map_err(|(_, e)| e)
     }
 }
 
+/// Parses a script of commands (see [script](crate::script)), pointing at the offending part of
+/// the script if it's malformed.
+pub fn parse(input: &str) -> Result<Vec<Command>, Diagnostic> {
+    crate::script::parse(input).map_err(|e| e.diagnostic(input))
+}
+
 #[derive(Debug, Error)]
 pub enum CommandParseError {
     #[error("{0} is not a valid command verb")]
@@ -55,4 +131,89 @@ pub enum CommandParseError {
 
     #[error("incomplete command")]
     IncompleteCommand,
+
+    #[error("{0} is not a setting, the only setting is aim")]
+    UnknownSetting(String),
+
+    #[error("unexpected {0} after the command")]
+    TrailingInput(String),
+
+    #[error("expected `{{` to open a block")]
+    ExpectedBlock,
+
+    #[error("`}}` without a block to close")]
+    UnexpectedClose,
+
+    #[error("the block opened on line {0} is never closed")]
+    UnclosedBlock(usize),
+
+    #[error("{0} is not a defined macro")]
+    UnknownMacro(String),
+
+    #[error("the macro {0} is already defined")]
+    DuplicateMacro(String),
+
+    #[error("{0} is a keyword, so it can't be used as a macro name")]
+    ReservedName(String),
+
+    #[error("macros can only be defined outside of blocks")]
+    NestedMacro,
+
+    #[error("the script expands to more than {0} commands")]
+    TooManyCommands(usize),
+
+    /// Any of the other errors, along with where it happened in a script.
+    #[error("line {line}, column {column}: {error}")]
+    At {
+        line: usize,
+        column: usize,
+        error: Box<CommandParseError>,
+    },
+}
+
+impl CommandParseError {
+    /// What should have been there instead.
+    fn expected(&self) -> String {
+        match self {
+            Self::UnknownVerb(_) => {
+                "`forward`, `back`, `up`, `down`, `set aim`, `repeat`, `macro` or a macro name"
+                    .into()
+            }
+            Self::BadInteger(_) => "a whole number".into(),
+            Self::IncompleteCommand => "a whole number amount after the verb".into(),
+            Self::UnknownSetting(_) => "`aim`".into(),
+            Self::TrailingInput(_) => "the end of the line".into(),
+            Self::ExpectedBlock => "`{` to open the block".into(),
+            Self::UnexpectedClose => "a command, since there's no block to close".into(),
+            Self::UnclosedBlock(_) => "a `}` closing this block later on".into(),
+            Self::UnknownMacro(name) => format!("a macro defined before this line, not `{}`", name),
+            Self::DuplicateMacro(name) => format!("a new macro name, `{}` is already taken", name),
+            Self::ReservedName(name) => {
+                format!("a macro name which isn't a keyword like `{}`", name)
+            }
+            Self::NestedMacro => "macro definitions to be outside of any block".into(),
+            Self::TooManyCommands(max) => format!("a script expanding to at most {} commands", max),
+            Self::At { error, .. } => error.expected(),
+        }
+    }
+
+    /// Points at where in `input` this error happened.
+    pub fn diagnostic(&self, input: &str) -> Diagnostic {
+        match self {
+            Self::At {
+                line,
+                column,
+                error,
+            } => {
+                let source = input.lines().nth(line - 1).unwrap_or_default();
+                Diagnostic::new(
+                    *line,
+                    *column,
+                    source.trim_end_matches('\r'),
+                    error.expected(),
+                )
+            }
+            other => Diagnostic::at_offset(input, 0, other.expected()),
+        }
+    }
 }
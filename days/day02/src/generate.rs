@@ -0,0 +1,28 @@
+use rand::Rng;
+
+/// How many commands the official input has.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// Generates `size` commands. The submarine never goes up past the surface.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut depth = 0;
+
+    (0..size)
+        .map(|_| {
+            let amount = rng.gen_range(1..10);
+            let direction = match rng.gen_range(0..3) {
+                0 => "forward",
+                1 if depth >= amount => {
+                    depth -= amount;
+                    "up"
+                }
+                _ => {
+                    depth += amount;
+                    "down"
+                }
+            };
+
+            format!("{} {}\n", direction, amount)
+        })
+        .collect()
+}
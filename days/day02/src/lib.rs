@@ -0,0 +1,151 @@
+use std::io::BufRead;
+
+use color_eyre::Result;
+use command::Command;
+use common::Day;
+use thiserror::Error;
+
+pub mod command;
+pub mod generate;
+pub mod plan;
+pub mod script;
+pub mod trace;
+
+pub trait Solver {
+    /// Moves the submarine, failing if it goes further than the position or aim can hold.
+    fn input(&mut self, cmd: Command) -> Result<(), SolverError>;
+    fn position(&self) -> &Position;
+
+    /// Where the submarine is aiming, for the solvers which keep track of it.
+    fn aim(&self) -> Option<i64> {
+        None
+    }
+}
+
+/// Scripts can repeat commands millions of times, so the position is kept in 64 bits and checked
+/// rather than left to wrap around.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum SolverError {
+    #[error("the submarine went further than a 64 bit position or aim can hold")]
+    Overflow,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub depth: i64,
+    pub horizontal: i64,
+}
+
+impl Position {
+    pub fn solution(&self) -> Result<i64, SolverError> {
+        self.depth
+            .checked_mul(self.horizontal)
+            .ok_or(SolverError::Overflow)
+    }
+}
+
+/// Adds `x` to `n`, failing rather than wrapping around.
+fn add(n: i64, x: i64) -> Result<i64, SolverError> {
+    n.checked_add(x).ok_or(SolverError::Overflow)
+}
+
+/// Multiplies `n` by `x`, failing rather than wrapping around.
+fn mul(n: i64, x: i64) -> Result<i64, SolverError> {
+    n.checked_mul(x).ok_or(SolverError::Overflow)
+}
+
+#[derive(Debug, Default)]
+pub struct Part1 {
+    pos: Position,
+}
+
+impl Solver for Part1 {
+    fn input(&mut self, cmd: Command) -> Result<(), SolverError> {
+        let pos = &mut self.pos;
+        match cmd {
+            Command::Forward(x) => pos.horizontal = add(pos.horizontal, x.into())?,
+            Command::Back(x) => pos.horizontal = add(pos.horizontal, -i64::from(x))?,
+            Command::Up(x) => pos.depth = add(pos.depth, -i64::from(x))?,
+            Command::Down(x) => pos.depth = add(pos.depth, x.into())?,
+            Command::SetAim(_) => {}
+        }
+        Ok(())
+    }
+
+    fn position(&self) -> &Position {
+        &self.pos
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Part2 {
+    pos: Position,
+    aim: i64,
+}
+
+impl Solver for Part2 {
+    fn input(&mut self, cmd: Command) -> Result<(), SolverError> {
+        let pos = &mut self.pos;
+        match cmd {
+            Command::Forward(x) => {
+                pos.horizontal = add(pos.horizontal, x.into())?;
+                pos.depth = add(pos.depth, mul(self.aim, x.into())?)?;
+            }
+            Command::Back(x) => {
+                pos.horizontal = add(pos.horizontal, -i64::from(x))?;
+                pos.depth = add(pos.depth, mul(self.aim, -i64::from(x))?)?;
+            }
+            Command::Up(x) => self.aim = add(self.aim, -i64::from(x))?,
+            Command::Down(x) => self.aim = add(self.aim, x.into())?,
+            Command::SetAim(x) => self.aim = x.into(),
+        }
+        Ok(())
+    }
+
+    fn position(&self) -> &Position {
+        &self.pos
+    }
+
+    fn aim(&self) -> Option<i64> {
+        Some(self.aim)
+    }
+}
+
+pub fn run<S: Solver, B: BufRead>(s: S, mut reader: B) -> Result<i64> {
+    // a script has to be read in full, since a repeat or macro isn't finished until its `}`
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+
+    Ok(feed(s, &command::parse(&input)?)?)
+}
+
+/// Like `run`, but for commands which have already been parsed.
+pub fn feed<S: Solver>(mut s: S, commands: &[Command]) -> Result<i64, SolverError> {
+    for cmd in commands {
+        s.input(*cmd)?;
+    }
+
+    s.position().solution()
+}
+
+pub struct Day02;
+
+impl Day for Day02 {
+    const DAY: usize = 2;
+
+    type Input = Vec<Command>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(command::parse(input)?)
+    }
+
+    fn part_1(input: &Self::Input) -> Result<Self::Part1> {
+        Ok(feed(Part1::default(), input)?)
+    }
+
+    fn part_2(input: &Self::Input) -> Result<Self::Part2> {
+        Ok(feed(Part2::default(), input)?)
+    }
+}
@@ -1,95 +1,120 @@
 use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+    io::{stdout, BufWriter, Read},
     path::PathBuf,
 };
 
-use color_eyre::Result;
-use command::Command;
+use color_eyre::{eyre::eyre, Result};
+use common::input;
+use day02::{
+    command,
+    plan::{plan, Constraints, Semantics},
+    run,
+    trace::{write_trace, Format},
+    Part1, Part2, Position,
+};
 use structopt::StructOpt;
 
-mod command;
-
 #[derive(StructOpt)]
 #[structopt(name = "aoc2021-day-2", about = "The second day of advent of code")]
 struct Cli {
-    #[structopt(parse(from_os_str), help = "the input file from adventofcode")]
-    input: PathBuf,
+    #[structopt(
+        parse(from_os_str),
+        help = "the input file from adventofcode. Reads from stdin if omitted or `-`"
+    )]
+    input: Option<PathBuf>,
 
     #[structopt(short = "2", help = "pass to run part 2")]
     part_2: bool,
-}
 
-impl Cli {
-    pub fn part_1(&self) -> bool {
-        !self.part_2
-    }
+    /// Writes where the submarine is after every command to stdout, so the route can be plotted.
+    /// The summary of the route goes to stderr to keep it out of the way.
+    #[structopt(
+        long,
+        help = "write the position after every command to stdout as csv or json (lines), with a summary of the route on stderr"
+    )]
+    trace: Option<Format>,
+
+    /// Works backwards from a position, printing the shortest script which reaches it instead of
+    /// running one. No input is read.
+    #[structopt(
+        long,
+        parse(try_from_str = parse_target),
+        allow_hyphen_values = true,
+        conflicts_with = "trace",
+        help = "print the shortest script which ends at this `horizontal,depth` position instead of running a script"
+    )]
+    plan: Option<Position>,
+
+    #[structopt(
+        long,
+        requires = "plan",
+        help = "the deepest the submarine can go while following the plan"
+    )]
+    max_depth: Option<i32>,
+
+    #[structopt(
+        long,
+        requires = "plan",
+        help = "the largest amount any command in the plan can have"
+    )]
+    max_step: Option<i32>,
 }
 
-trait Solver {
-    fn input(&mut self, cmd: Command);
-    fn position(&self) -> &Position;
-}
+fn parse_target(s: &str) -> Result<Position> {
+    let (horizontal, depth) = s
+        .split_once(',')
+        .ok_or_else(|| eyre!("expected `horizontal,depth`, not {}", s))?;
 
-#[derive(Debug, Default)]
-struct Position {
-    pub depth: i32,
-    pub horizontal: i32,
+    Ok(Position {
+        horizontal: horizontal.trim().parse()?,
+        depth: depth.trim().parse()?,
+    })
 }
 
-impl Position {
-    fn solution(&self) -> i32 {
-        self.depth * self.horizontal
+impl Cli {
+    pub fn part_1(&self) -> bool {
+        !self.part_2
     }
 }
 
-#[derive(Debug, Default)]
-struct Part1 {
-    pos: Position,
-}
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let opts = Cli::from_args();
 
-impl Solver for Part1 {
-    fn input(&mut self, cmd: Command) {
-        match cmd {
-            Command::Forward(x) => self.pos.horizontal += x,
-            Command::Up(x) => self.pos.depth -= x,
-            Command::Down(x) => self.pos.depth += x,
+    if let Some(target) = opts.plan {
+        let semantics = if opts.part_1() {
+            Semantics::Part1
+        } else {
+            Semantics::Part2
+        };
+        let constraints = Constraints {
+            max_depth: opts.max_depth,
+            max_step: opts.max_step,
+        };
+
+        for command in plan(target, semantics, constraints)? {
+            println!("{}", command);
         }
+        return Ok(());
     }
 
-    fn position(&self) -> &Position {
-        &self.pos
-    }
-}
+    let mut reader = input::open(opts.input.as_deref())?;
 
-#[derive(Debug, Default)]
-struct Part2 {
-    pos: Position,
-    aim: i32,
-}
+    if let Some(format) = opts.trace {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        let commands = command::parse(&input)?;
+        let writer = BufWriter::new(stdout().lock());
 
-impl Solver for Part2 {
-    fn input(&mut self, cmd: Command) {
-        match cmd {
-            Command::Forward(x) => {
-                self.pos.horizontal += x;
-                self.pos.depth += self.aim * x;
-            }
-            Command::Up(x) => self.aim -= x,
-            Command::Down(x) => self.aim += x,
-        }
-    }
+        let summary = if opts.part_1() {
+            write_trace(Part1::default(), &commands, format, writer)?
+        } else {
+            write_trace(Part2::default(), &commands, format, writer)?
+        };
 
-    fn position(&self) -> &Position {
-        &self.pos
+        eprintln!("{}", summary);
+        return Ok(());
     }
-}
-
-fn main() -> Result<()> {
-    color_eyre::install()?;
-    let opts = Cli::from_args();
-
-    let reader = BufReader::new(File::open(&opts.input)?);
 
     let solution = if opts.part_1() {
         run(Part1::default(), reader)?
@@ -101,11 +126,3 @@ fn main() -> Result<()> {
 
     Ok(())
 }
-
-fn run<S: Solver, B: BufRead>(mut s: S, reader: B) -> Result<i32> {
-    for line in reader.lines() {
-        s.input(line?.parse()?);
-    }
-
-    Ok(s.position().solution())
-}
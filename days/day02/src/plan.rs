@@ -0,0 +1,662 @@
+//! Working backwards from a position: finding the shortest list of commands which takes the
+//! submarine there. Every plan is run back through [Part1] or [Part2] before it's handed out, so a
+//! plan which doesn't do what it should is caught here rather than by whoever follows it.
+//!
+//! Without a limit on the step size the shortest plans are short enough to write down directly. No
+//! plan under [Part1] needs more than one command along each axis, and no plan under [Part2] needs
+//! more than three commands: moving along, turning to the right aim, then moving the rest of the
+//! way. With a step limit the [Part2] plans are found by an A* search instead. The search is
+//! quick for plans up to a few dozen commands, but targets which need hundreds of small steps are
+//! beyond it, and it gives up with [PlanError::SearchLimit] rather than running out of memory.
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+};
+
+use thiserror::Error;
+
+use crate::{command::Command, trace::trace, Part1, Part2, Position, Solver, SolverError};
+
+/// The most positions the search will keep track of before giving up.
+pub const MAX_STATES: usize = 1_000_000;
+
+/// How the commands move the submarine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Semantics {
+    /// Up and down change the depth, as in [Part1]
+    Part1,
+
+    /// Up and down change the aim, as in [Part2]
+    Part2,
+}
+
+/// Limits on the plans which are allowed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Constraints {
+    /// The deepest the submarine can be after any command
+    pub max_depth: Option<i32>,
+
+    /// The largest amount any one command can have. For `set aim` this limits how far the aim
+    /// can be set from 0.
+    pub max_step: Option<i32>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PlanError {
+    #[error("the maximum step has to be at least 1, not {0}")]
+    InvalidStep(i32),
+
+    #[error("the maximum depth can't be above the surface, but it was {0}")]
+    InvalidMaxDepth(i32),
+
+    #[error("the target depth {depth} is below the maximum depth {max_depth}")]
+    TooDeep { depth: i64, max_depth: i32 },
+
+    #[error("the plan needs a command amount which doesn't fit in an i32")]
+    OutOfRange,
+
+    #[error("gave up after looking at {0} positions without finding a plan")]
+    SearchLimit(usize),
+
+    #[error("the plan was meant to reach {expected:?} but the solver ended up at {actual:?}")]
+    Unverified {
+        expected: Position,
+        actual: Position,
+    },
+
+    #[error("the plan goes down to {depth}, past the maximum depth {max_depth}")]
+    TooDeepOnTheWay { depth: i64, max_depth: i32 },
+
+    #[error(transparent)]
+    Solver(#[from] SolverError),
+}
+
+/// Finds one of the shortest plans which takes the submarine from the start to `target`, moving
+/// under the given semantics and within the constraints.
+pub fn plan(
+    target: Position,
+    semantics: Semantics,
+    constraints: Constraints,
+) -> Result<Vec<Command>, PlanError> {
+    let step = match constraints.max_step {
+        Some(step) if step < 1 => return Err(PlanError::InvalidStep(step)),
+        step => step.map(i64::from),
+    };
+
+    match constraints.max_depth {
+        Some(max_depth) if max_depth < 0 => return Err(PlanError::InvalidMaxDepth(max_depth)),
+        Some(max_depth) if target.depth > i64::from(max_depth) => {
+            return Err(PlanError::TooDeep {
+                depth: target.depth,
+                max_depth,
+            })
+        }
+        _ => {}
+    }
+
+    let goal = (target.horizontal, target.depth);
+    let commands = match (semantics, step) {
+        (Semantics::Part1, step) => plan_part_1(goal, step)?,
+        (Semantics::Part2, None) => plan_part_2(goal)?,
+        (Semantics::Part2, Some(step)) => match plan_part_2(goal) {
+            // the shortest plan is still the shortest with a step limit, as long as it fits
+            Ok(commands) if commands.iter().all(|c| i64::from(c.amount()).abs() <= step) => {
+                commands
+            }
+            // the closed form can need amounts which don't fit in an i32, but the search's smaller
+            // steps always do
+            Ok(_) | Err(PlanError::OutOfRange) => search(goal, step, constraints.max_depth)?,
+            Err(error) => return Err(error),
+        },
+    };
+
+    match semantics {
+        Semantics::Part1 => verify(Part1::default(), &commands, target, constraints)?,
+        Semantics::Part2 => verify(Part2::default(), &commands, target, constraints)?,
+    }
+
+    Ok(commands)
+}
+
+/// Runs the plan through the solver, checking it ends up at the target without going too deep.
+fn verify<S: Solver>(
+    solver: S,
+    commands: &[Command],
+    target: Position,
+    constraints: Constraints,
+) -> Result<(), PlanError> {
+    let mut end = Position::default();
+
+    for step in trace(solver, commands) {
+        let step = step?;
+        if let Some(max_depth) = constraints.max_depth {
+            if step.position.depth > i64::from(max_depth) {
+                return Err(PlanError::TooDeepOnTheWay {
+                    depth: step.position.depth,
+                    max_depth,
+                });
+            }
+        }
+
+        end = step.position;
+    }
+
+    if end != target {
+        return Err(PlanError::Unverified {
+            expected: target,
+            actual: end,
+        });
+    }
+
+    Ok(())
+}
+
+/// Under [Part1] the two axes don't affect each other, so each one just needs as few commands as
+/// the step size allows. The depth only ever heads towards the target, so it never goes deeper
+/// than the target does.
+fn plan_part_1(
+    (horizontal, depth): (i64, i64),
+    step: Option<i64>,
+) -> Result<Vec<Command>, PlanError> {
+    let mut commands = moves(horizontal, step, Command::Forward, Command::Back)?;
+    commands.extend(moves(depth, step, Command::Down, Command::Up)?);
+    Ok(commands)
+}
+
+/// Under [Part2] the first command can't change the depth, since the aim starts off at 0, so
+/// reaching a new depth takes at least two commands: aiming, then moving. That only lands on the
+/// target when the horizontal distance divides the depth, and otherwise three commands always do:
+/// move all but one step, aim at the depth, and move that last step. The depth only changes on
+/// the last command, so it never goes deeper than the target does.
+fn plan_part_2((horizontal, depth): (i64, i64)) -> Result<Vec<Command>, PlanError> {
+    if depth == 0 {
+        return moves(horizontal, None, Command::Forward, Command::Back);
+    }
+
+    let mut commands = Vec::new();
+    let last = if horizontal != 0 && depth % horizontal == 0 {
+        horizontal
+    } else {
+        // horizontal can't be 1 or -1 here, since they divide everything, so stepping back from it
+        // never passes 0
+        let last = if horizontal > 0 { 1 } else { -1 };
+        commands.extend(moves(
+            horizontal - last,
+            None,
+            Command::Forward,
+            Command::Back,
+        )?);
+        last
+    };
+
+    commands.extend(moves(depth / last, None, Command::Down, Command::Up)?);
+    commands.extend(moves(last, None, Command::Forward, Command::Back)?);
+    Ok(commands)
+}
+
+/// Covers `distance` in as few commands as possible, using `forwards` when it's positive and
+/// `backwards` when it's negative.
+fn moves(
+    distance: i64,
+    step: Option<i64>,
+    forwards: fn(i32) -> Command,
+    backwards: fn(i32) -> Command,
+) -> Result<Vec<Command>, PlanError> {
+    let direction = if distance < 0 { backwards } else { forwards };
+    let mut remaining = distance.abs();
+    let step = step.unwrap_or(remaining);
+
+    let mut commands = Vec::new();
+    while remaining > 0 {
+        let amount = remaining.min(step);
+        commands.push(direction(amount_of(amount)?));
+        remaining -= amount;
+    }
+
+    Ok(commands)
+}
+
+fn amount_of(amount: i64) -> Result<i32, PlanError> {
+    i32::try_from(amount).map_err(|_| PlanError::OutOfRange)
+}
+
+/// What the last command in a plan was. The search only looks at plans in a canonical order, which
+/// depends on what came before (see [successors]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Last {
+    Start,
+    Move { backwards: bool, full: bool },
+    Turn { up: bool, full: bool },
+    SetAim,
+}
+
+/// Where the submarine is during the search, along with how it got there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Node {
+    horizontal: i64,
+    depth: i64,
+    aim: i64,
+    last: Last,
+}
+
+/// The length of the shortest plan found to each node, and the command which got there from the
+/// node before.
+type Seen = HashMap<Node, (usize, Option<(Node, Command)>)>;
+
+/// An A* search for the shortest plan under [Part2] when every command is limited to `step`.
+fn search(goal: (i64, i64), step: i64, max_depth: Option<i32>) -> Result<Vec<Command>, PlanError> {
+    let max_depth = max_depth.map_or(i64::MAX, i64::from);
+    let start = Node {
+        horizontal: 0,
+        depth: 0,
+        aim: 0,
+        last: Last::Start,
+    };
+
+    let mut seen = Seen::new();
+    seen.insert(start, (0, None));
+
+    // ordered by the estimated plan length, then the longest plan so far so the search dives
+    // towards the goal rather than widening out
+    let mut queue = BinaryHeap::new();
+    queue.push((Reverse(estimate(start, goal, step)), 0, start));
+
+    while let Some((_, length, node)) = queue.pop() {
+        if seen[&node].0 < length {
+            // a shorter way to this node turned up after this one was queued
+            continue;
+        }
+
+        if (node.horizontal, node.depth) == goal {
+            return Ok(path(&seen, node));
+        }
+
+        for (command, next) in successors(node, step) {
+            if next.depth > max_depth {
+                continue;
+            }
+
+            let length = length + 1;
+            match seen.entry(next) {
+                Entry::Occupied(entry) if entry.get().0 <= length => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((length, Some((node, command))));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((length, Some((node, command))));
+                }
+            }
+
+            if seen.len() > MAX_STATES {
+                return Err(PlanError::SearchLimit(MAX_STATES));
+            }
+
+            queue.push((Reverse(length + estimate(next, goal, step)), length, next));
+        }
+    }
+
+    unreachable!("the search space is unbounded, so it's always possible to keep searching")
+}
+
+/// The commands worth trying after reaching `node`, along with where they lead.
+///
+/// Lots of plans only differ in the order of their commands, so only one order is tried. The
+/// moves between two changes of aim all go the same way, with full steps before the one partial
+/// step. Changes of aim in a row all go the same way too, again with full steps first, and `set
+/// aim` is only used where `up` or `down` couldn't get there in one go. Any plan can be put in
+/// this order without getting longer, and without going any deeper along the way.
+fn successors(node: Node, step: i64) -> impl Iterator<Item = (Command, Node)> {
+    let Node {
+        horizontal,
+        depth,
+        aim,
+        last,
+    } = node;
+
+    let moves: &[bool] = match last {
+        Last::Move { full: false, .. } => &[],
+        Last::Move {
+            backwards: false, ..
+        } => &[false],
+        Last::Move {
+            backwards: true, ..
+        } => &[true],
+        _ => &[false, true],
+    };
+
+    // the successors are generated as they're needed, since there are about 4 times `step` of
+    // them and the search may give up long before looking at them all
+    let moves = moves.iter().flat_map(move |&backwards| {
+        let sign = if backwards { -1 } else { 1 };
+        (1..=step).map(move |x| {
+            let command = if backwards {
+                Command::Back(x as i32)
+            } else {
+                Command::Forward(x as i32)
+            };
+
+            (
+                command,
+                Node {
+                    horizontal: horizontal + sign * x,
+                    depth: depth.saturating_add((sign * x).saturating_mul(aim)),
+                    aim,
+                    last: Last::Move {
+                        backwards,
+                        full: x == step,
+                    },
+                },
+            )
+        })
+    });
+
+    let turns: &[bool] = match last {
+        Last::Start | Last::Move { .. } => &[false, true],
+        Last::Turn { full: false, .. } => &[],
+        Last::Turn { up, .. } if up => &[true],
+        Last::Turn { .. } => &[false],
+        // only turning further than `set aim` could have
+        Last::SetAim if aim > 0 => &[false],
+        Last::SetAim if aim < 0 => &[true],
+        Last::SetAim => &[],
+    };
+
+    let turns = turns.iter().flat_map(move |&up| {
+        (1..=step).filter_map(move |x| {
+            let (command, aim) = if up {
+                (Command::Up(x as i32), aim - x)
+            } else {
+                (Command::Down(x as i32), aim + x)
+            };
+
+            if last == Last::SetAim && aim.abs() <= step {
+                return None;
+            }
+
+            Some((
+                command,
+                Node {
+                    aim,
+                    last: Last::Turn {
+                        up,
+                        full: x == step,
+                    },
+                    ..node
+                },
+            ))
+        })
+    });
+
+    let set_aim = matches!(last, Last::Move { .. });
+    let set_aims = (-step..=step)
+        .filter(move |a| set_aim && (a - aim).abs() > step)
+        .map(move |a| {
+            (
+                Command::SetAim(a as i32),
+                Node {
+                    aim: a,
+                    last: Last::SetAim,
+                    ..node
+                },
+            )
+        });
+
+    moves.chain(turns).chain(set_aims)
+}
+
+/// A lower bound on how many more commands it takes to get from `node` to the goal.
+///
+/// Say the rest of the plan turns `j` times and moves `m` times. Each move goes at most `step`
+/// along, so `m` has to cover the horizontal distance, and each turn leaves the aim at most `step`
+/// further from 0, so the moves dive at most `step * (aim + j * step)` each and `m` has to cover
+/// the depth too. The bound is the smallest `j + m` which does both.
+fn estimate(node: Node, (goal_h, goal_d): (i64, i64), step: i64) -> usize {
+    let along = (goal_h - node.horizontal).abs();
+    let along = (along + step - 1) / step;
+
+    let down = (goal_d - node.depth).abs();
+    if down == 0 {
+        return along as usize;
+    }
+
+    let aim = node.aim.abs();
+    let mut best = i64::MAX;
+    let mut turns = 0;
+    while turns < best {
+        let dive = step.saturating_mul(aim + turns * step);
+        if dive > 0 {
+            let moves = along.max((down + dive - 1) / dive);
+            best = best.min(turns + moves);
+        }
+        turns += 1;
+    }
+
+    best as usize
+}
+
+/// Follows the commands back from `node` to the start.
+fn path(seen: &Seen, mut node: Node) -> Vec<Command> {
+    let mut commands = Vec::new();
+    while let Some((previous, command)) = seen[&node].1 {
+        commands.push(command);
+        node = previous;
+    }
+
+    commands.reverse();
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashSet, VecDeque};
+
+    use super::{plan, Constraints, PlanError, Semantics, MAX_STATES};
+    use crate::{feed, Part1, Part2, Position};
+
+    fn position(horizontal: i32, depth: i32) -> Position {
+        Position {
+            horizontal: horizontal.into(),
+            depth: depth.into(),
+        }
+    }
+
+    #[test]
+    fn test_part_1() {
+        let unlimited = Constraints::default();
+        assert_eq!(
+            0,
+            plan(position(0, 0), Semantics::Part1, unlimited)
+                .unwrap()
+                .len()
+        );
+        assert_eq!(
+            2,
+            plan(position(15, 10), Semantics::Part1, unlimited)
+                .unwrap()
+                .len()
+        );
+        assert_eq!(
+            1,
+            plan(position(-4, 0), Semantics::Part1, unlimited)
+                .unwrap()
+                .len()
+        );
+
+        let limited = Constraints {
+            max_step: Some(9),
+            ..Constraints::default()
+        };
+        let commands = plan(position(15, -10), Semantics::Part1, limited).unwrap();
+        assert_eq!(4, commands.len());
+        assert_eq!(Ok(-150), feed(Part1::default(), &commands));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let unlimited = Constraints::default();
+        for (horizontal, depth, length) in [
+            (0, 0, 0),
+            (5, 0, 1),
+            (15, 60, 2),
+            (-3, 12, 2),
+            (3, 5, 3),
+            (0, 7, 3),
+            (-7, -9, 3),
+            (i32::MIN, 3, 3),
+        ] {
+            let commands = plan(position(horizontal, depth), Semantics::Part2, unlimited).unwrap();
+            assert_eq!(length, commands.len(), "plan for {},{}", horizontal, depth);
+        }
+
+        assert_eq!(
+            Err(PlanError::OutOfRange),
+            plan(position(3, i32::MIN), Semantics::Part2, unlimited)
+        );
+        assert_eq!(
+            Err(PlanError::TooDeep {
+                depth: 60,
+                max_depth: 50
+            }),
+            plan(
+                position(15, 60),
+                Semantics::Part2,
+                Constraints {
+                    max_depth: Some(50),
+                    max_step: None
+                }
+            )
+        );
+    }
+
+    /// The length of the shortest plan under part 2, found by a plain breadth first search through
+    /// every command rather than just the ones in canonical order.
+    fn shortest(target: (i64, i64), step: i64, max_depth: i64) -> usize {
+        let mut seen = HashSet::from([(0, 0, 0)]);
+        let mut queue = VecDeque::from([((0, 0, 0), 0)]);
+
+        while let Some((state, length)) = queue.pop_front() {
+            if (state.0, state.1) == target {
+                return length;
+            }
+
+            let (horizontal, depth, aim) = state;
+            let moves = (1..=step).flat_map(|x| {
+                [
+                    (horizontal + x, depth + aim * x, aim),
+                    (horizontal - x, depth - aim * x, aim),
+                    (horizontal, depth, aim + x),
+                    (horizontal, depth, aim - x),
+                ]
+            });
+            let set_aim = (-step..=step).map(|a| (horizontal, depth, a));
+
+            for next in moves.chain(set_aim) {
+                if next.1 <= max_depth && seen.insert(next) {
+                    queue.push_back((next, length + 1));
+                }
+            }
+        }
+
+        unreachable!()
+    }
+
+    #[test]
+    fn test_search_is_shortest() {
+        for (horizontal, depth) in [(3, 5), (0, 7), (-2, 9), (4, -6), (5, 11), (1, 1)] {
+            for (step, max_depth) in [(2, 100), (3, 100), (3, 11)] {
+                let constraints = Constraints {
+                    max_depth: Some(max_depth),
+                    max_step: Some(step),
+                };
+                let target = position(horizontal, depth);
+                let commands = plan(target, Semantics::Part2, constraints).unwrap();
+
+                assert_eq!(
+                    shortest(
+                        (horizontal.into(), depth.into()),
+                        step.into(),
+                        max_depth.into()
+                    ),
+                    commands.len(),
+                    "plan for {},{} with steps of {}",
+                    horizontal,
+                    depth,
+                    step
+                );
+                assert_eq!(target.solution(), feed(Part2::default(), &commands));
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_limit() {
+        let constraints = Constraints {
+            max_depth: Some(5000),
+            max_step: Some(9),
+        };
+        let target = position(200, 5000);
+        let commands = plan(target, Semantics::Part2, constraints).unwrap();
+
+        // 23 moves to get 200 along, and 3 turns to aim steeply enough to get 5000 down
+        assert_eq!(26, commands.len());
+        assert!(commands.iter().all(|c| (1..=9).contains(&c.amount())));
+        assert_eq!(target.solution(), feed(Part2::default(), &commands));
+    }
+
+    #[test]
+    fn test_huge_step() {
+        // a single expansion has billions of successors, which the search has to give up on
+        // without building them all first
+        let constraints = Constraints {
+            max_depth: None,
+            max_step: Some(1_000_000_000),
+        };
+
+        assert_eq!(
+            Err(PlanError::SearchLimit(MAX_STATES)),
+            plan(position(2, 1_999_999_999), Semantics::Part2, constraints)
+        );
+    }
+
+    #[test]
+    fn test_closed_form_out_of_range() {
+        // the closed form would turn down by 2^31 to get there, which doesn't fit in an i32
+        let target = position(-3, i32::MIN);
+        assert_eq!(
+            Err(PlanError::OutOfRange),
+            plan(target, Semantics::Part2, Constraints::default())
+        );
+
+        // so with a step limit it's left to the search, which can't get that far either
+        let constraints = Constraints {
+            max_depth: None,
+            max_step: Some(1000),
+        };
+        assert_eq!(
+            Err(PlanError::SearchLimit(MAX_STATES)),
+            plan(target, Semantics::Part2, constraints)
+        );
+    }
+
+    #[test]
+    fn test_invalid_constraints() {
+        let target = position(1, 1);
+        let step = Constraints {
+            max_step: Some(0),
+            ..Constraints::default()
+        };
+        let depth = Constraints {
+            max_depth: Some(-1),
+            ..Constraints::default()
+        };
+
+        assert_eq!(
+            Err(PlanError::InvalidStep(0)),
+            plan(target, Semantics::Part1, step)
+        );
+        assert_eq!(
+            Err(PlanError::InvalidMaxDepth(-1)),
+            plan(target, Semantics::Part2, depth)
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! Mission scripts: the puzzle's list of commands, extended so longer missions are easier to write.
+//!
+//! ```text
+//! # comments run to the end of the line, and blank lines are ignored
+//! forward 5
+//! back 2
+//! set aim 3
+//!
+//! # blocks can be repeated
+//! repeat 3 {
+//!     down 1
+//!     forward 2
+//! }
+//!
+//! # macros have to be defined before they're used, outside of any block
+//! macro dive {
+//!     down 10
+//!     forward 1
+//! }
+//! dive
+//! ```
+//!
+//! Every repeat and macro is expanded when the script is parsed, so the result is a plain list of
+//! commands which any [Solver](crate::Solver) can run.
+use std::collections::HashMap;
+
+use crate::command::{parse_tokens, tokenize, Command, CommandParseError, Token};
+
+/// The most commands a script can expand to. Without a limit a few nested repeats could ask for
+/// more commands than there is memory for.
+pub const MAX_COMMANDS: usize = 10_000_000;
+
+/// Words which can't be used as macro names.
+const KEYWORDS: [&str; 7] = ["forward", "back", "up", "down", "set", "repeat", "macro"];
+
+enum Block<'a> {
+    /// The top level of the script, outside of any block.
+    Script,
+    Repeat(usize),
+    Macro(&'a str),
+}
+
+/// A block which is still open, along with the commands inside it so far.
+struct Frame<'a> {
+    block: Block<'a>,
+
+    /// The line and column of the keyword which opened the block
+    opened: (usize, usize),
+
+    commands: Vec<Command>,
+}
+
+/// Parses and expands a mission script. Errors carry the line and column they happened on.
+pub fn parse(input: &str) -> Result<Vec<Command>, CommandParseError> {
+    let mut macros: HashMap<&str, Vec<Command>> = HashMap::new();
+    let mut stack = vec![Frame {
+        block: Block::Script,
+        opened: (1, 1),
+        commands: Vec::new(),
+    }];
+
+    for (i, source) in input.lines().enumerate() {
+        let line = i + 1;
+        let code = source.split('#').next().unwrap_or_default().trim_end();
+        let tokens = tokenize(code);
+        let at = |offset: usize, error: CommandParseError| CommandParseError::At {
+            line,
+            column: column(code, offset),
+            error: Box::new(error),
+        };
+
+        match tokens.as_slice() {
+            [] => {}
+
+            [(offset, "}")] => {
+                let frame = stack.pop().expect("the script frame is never closed");
+                let parent = match stack.last_mut() {
+                    Some(parent) => parent,
+                    None => return Err(at(*offset, CommandParseError::UnexpectedClose)),
+                };
+
+                match frame.block {
+                    Block::Repeat(count) => {
+                        let total = frame
+                            .commands
+                            .len()
+                            .checked_mul(count)
+                            .and_then(|n| n.checked_add(parent.commands.len()))
+                            .filter(|&n| n <= MAX_COMMANDS);
+                        if total.is_none() {
+                            return Err(at(
+                                *offset,
+                                CommandParseError::TooManyCommands(MAX_COMMANDS),
+                            ));
+                        }
+
+                        for _ in 0..count {
+                            parent.commands.extend_from_slice(&frame.commands);
+                        }
+                    }
+                    Block::Macro(name) => {
+                        macros.insert(name, frame.commands);
+                    }
+                    Block::Script => unreachable!("the script frame is always at the bottom"),
+                }
+            }
+
+            [(keyword, "repeat"), rest @ ..] => {
+                let (offset, count) = rest
+                    .first()
+                    .ok_or_else(|| at(code.len(), CommandParseError::IncompleteCommand))?;
+                let count = count
+                    .parse()
+                    .map_err(|e| at(*offset, CommandParseError::BadInteger(e)))?;
+                open_block(&rest[1..], code.len()).map_err(|(o, e)| at(o, e))?;
+
+                stack.push(Frame {
+                    block: Block::Repeat(count),
+                    opened: (line, column(code, *keyword)),
+                    commands: Vec::new(),
+                });
+            }
+
+            [(keyword, "macro"), rest @ ..] => {
+                if stack.len() > 1 {
+                    return Err(at(*keyword, CommandParseError::NestedMacro));
+                }
+
+                let &(offset, name) = rest
+                    .first()
+                    .ok_or_else(|| at(code.len(), CommandParseError::IncompleteCommand))?;
+                if KEYWORDS.contains(&name) {
+                    return Err(at(offset, CommandParseError::ReservedName(name.into())));
+                }
+                if macros.contains_key(name) {
+                    return Err(at(offset, CommandParseError::DuplicateMacro(name.into())));
+                }
+                open_block(&rest[1..], code.len()).map_err(|(o, e)| at(o, e))?;
+
+                stack.push(Frame {
+                    block: Block::Macro(name),
+                    opened: (line, column(code, *keyword)),
+                    commands: Vec::new(),
+                });
+            }
+
+            [(offset, name)] if !KEYWORDS.contains(name) => {
+                let body = macros.get(name).ok_or_else(|| {
+                    at(*offset, CommandParseError::UnknownMacro(name.to_string()))
+                })?;
+                let commands = &mut stack.last_mut().expect("there's always a frame").commands;
+                if commands.len() + body.len() > MAX_COMMANDS {
+                    return Err(at(
+                        *offset,
+                        CommandParseError::TooManyCommands(MAX_COMMANDS),
+                    ));
+                }
+
+                commands.extend_from_slice(body);
+            }
+
+            tokens => {
+                let command = parse_tokens(tokens, code.len()).map_err(|(o, e)| at(o, e))?;
+                let commands = &mut stack.last_mut().expect("there's always a frame").commands;
+                if commands.len() >= MAX_COMMANDS {
+                    return Err(at(0, CommandParseError::TooManyCommands(MAX_COMMANDS)));
+                }
+
+                commands.push(command);
+            }
+        }
+    }
+
+    let frame = stack.pop().expect("the script frame is never closed");
+    match frame.block {
+        Block::Script => Ok(frame.commands),
+        _ => {
+            let (line, column) = frame.opened;
+            Err(CommandParseError::At {
+                line,
+                column,
+                error: Box::new(CommandParseError::UnclosedBlock(line)),
+            })
+        }
+    }
+}
+
+/// Checks that the rest of a line which opens a block is just the `{`.
+fn open_block(rest: &[Token], end: usize) -> Result<(), (usize, CommandParseError)> {
+    match rest {
+        [(_, "{")] => Ok(()),
+        [(_, "{"), (offset, extra), ..] => {
+            Err((*offset, CommandParseError::TrailingInput(extra.to_string())))
+        }
+        [(offset, _), ..] => Err((*offset, CommandParseError::ExpectedBlock)),
+        [] => Err((end, CommandParseError::ExpectedBlock)),
+    }
+}
+
+/// The column, counting characters from 1, of the byte `offset` into `line`.
+fn column(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::{
+        command::{Command, CommandParseError},
+        feed, Part1, Part2, SolverError,
+    };
+
+    const SCRIPT: &str = "\
+# a mission script
+forward 5
+
+set aim 2   # aim straight down
+repeat 2 {
+    forward 3
+    repeat 2 {
+        up 1
+    }
+}
+macro dive {
+    down 10
+    forward 1
+}
+dive
+back 4
+";
+
+    #[test]
+    fn test_script() {
+        use Command::*;
+
+        let commands = parse(SCRIPT).unwrap();
+        assert_eq!(
+            vec![
+                Forward(5),
+                SetAim(2),
+                Forward(3),
+                Up(1),
+                Up(1),
+                Forward(3),
+                Up(1),
+                Up(1),
+                Down(10),
+                Forward(1),
+                Back(4),
+            ],
+            commands
+        );
+
+        // part 1 ignores aim: 5 + 3 + 3 + 1 - 4 across, -4 + 10 down
+        assert_eq!(Ok(8 * 6), feed(Part1::default(), &commands));
+
+        // part 2 dives by aim * forward: 2 * 3 + 0 * 3 + 8 * 1 - 8 * 4
+        assert_eq!(Ok(8 * (6 + 8 - 32)), feed(Part2::default(), &commands));
+    }
+
+    #[test]
+    fn test_plain_commands() {
+        let input = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+        assert_eq!(Ok(150), feed(Part1::default(), &parse(input).unwrap()));
+        assert_eq!(Ok(900), feed(Part2::default(), &parse(input).unwrap()));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // a short script can still go a very long way
+        let commands = parse("repeat 10000 {\n    forward 1000\n    down 1000\n}\n").unwrap();
+        assert_eq!(
+            Ok(10_000_000 * 10_000_000),
+            feed(Part1::default(), &commands)
+        );
+
+        // the depth is 1000 * 1000 * (0 + 1 + ... + 9999), which fits, but times the distance
+        // forward it doesn't
+        assert_eq!(
+            Err(SolverError::Overflow),
+            feed(Part2::default(), &commands)
+        );
+
+        let commands =
+            parse("set aim 2147483647\nrepeat 3 {\n    forward 2147483647\n}\n").unwrap();
+        assert_eq!(
+            Err(SolverError::Overflow),
+            feed(Part2::default(), &commands)
+        );
+    }
+
+    fn error(input: &str) -> (usize, usize, CommandParseError) {
+        match parse(input).unwrap_err() {
+            CommandParseError::At {
+                line,
+                column,
+                error,
+            } => (line, column, *error),
+            other => panic!("{} has no location", other),
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            error("forward 1\n  sideways 2\n"),
+            (2, 3, CommandParseError::UnknownVerb(_))
+        ));
+        assert!(matches!(
+            error("set depth 2"),
+            (1, 5, CommandParseError::UnknownSetting(_))
+        ));
+        assert!(matches!(
+            error("forward 1 2"),
+            (1, 11, CommandParseError::TrailingInput(_))
+        ));
+        assert!(matches!(
+            error("repeat 2 {\n  forward 1\n"),
+            (1, 1, CommandParseError::UnclosedBlock(1))
+        ));
+        assert!(matches!(
+            error("forward 1\n}\n"),
+            (2, 1, CommandParseError::UnexpectedClose)
+        ));
+        assert!(matches!(
+            error("repeat x {\n}"),
+            (1, 8, CommandParseError::BadInteger(_))
+        ));
+        assert!(matches!(
+            error("repeat 2\n}"),
+            (1, 9, CommandParseError::ExpectedBlock)
+        ));
+        assert!(matches!(
+            error("macro loop {\n  loop\n}"),
+            (2, 3, CommandParseError::UnknownMacro(_))
+        ));
+        assert!(matches!(
+            error("repeat 2 {\n  macro m {\n  }\n}"),
+            (2, 3, CommandParseError::NestedMacro)
+        ));
+        assert!(matches!(
+            error("macro up {\n}"),
+            (1, 7, CommandParseError::ReservedName(_))
+        ));
+        assert!(matches!(
+            error("repeat 100000 {\n repeat 100000 {\n  forward 1\n }\n}"),
+            (5, 1, CommandParseError::TooManyCommands(_))
+        ));
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let input = "forward 1\n  sideways 2\n";
+        let diagnostic = parse(input).unwrap_err().diagnostic(input);
+
+        assert_eq!((2, 3), (diagnostic.line, diagnostic.column));
+        assert_eq!("  sideways 2", diagnostic.source);
+    }
+}
@@ -0,0 +1,272 @@
+//! Recording the route the submarine takes, rather than just where it ends up. A trace has a
+//! [Step] for every command with the position after it, which can be written out as CSV or JSON
+//! lines for plotting, along with a [Summary] of the route.
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    str::FromStr,
+};
+
+use serde_json::json;
+use thiserror::Error;
+
+use crate::{command::Command, Position, Solver, SolverError};
+
+/// Where the submarine was after a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// Which command this was, counting from 0
+    pub index: usize,
+    pub command: Command,
+    pub position: Position,
+
+    /// The aim after the command, if the solver keeps track of it
+    pub aim: Option<i64>,
+}
+
+/// Runs the commands through the solver, yielding a [Step] after each one, or an error for the
+/// command which took the submarine out of range.
+pub fn trace<'c, S: Solver + 'c>(
+    mut solver: S,
+    commands: &'c [Command],
+) -> impl Iterator<Item = Result<Step, SolverError>> + 'c {
+    commands.iter().enumerate().map(move |(index, &command)| {
+        solver.input(command)?;
+
+        Ok(Step {
+            index,
+            command,
+            position: *solver.position(),
+            aim: solver.aim(),
+        })
+    })
+}
+
+/// The ways a trace can be written out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Comma separated values, with a header row
+    Csv,
+
+    /// One JSON object per line
+    Json,
+}
+
+impl Format {
+    /// The first line of the output, if the format has one.
+    fn header(self, aim: bool) -> Option<&'static str> {
+        match (self, aim) {
+            (Self::Csv, false) => Some("index,command,horizontal,depth"),
+            (Self::Csv, true) => Some("index,command,horizontal,depth,aim"),
+            (Self::Json, _) => None,
+        }
+    }
+
+    fn row(self, step: &Step) -> String {
+        let Step {
+            index,
+            command,
+            position,
+            aim,
+        } = step;
+
+        match self {
+            Self::Csv => {
+                let mut row = format!(
+                    "{},{},{},{}",
+                    index, command, position.horizontal, position.depth
+                );
+                if let Some(aim) = aim {
+                    row.push_str(&format!(",{}", aim));
+                }
+                row
+            }
+            Self::Json => {
+                let mut row = json!({
+                    "index": index,
+                    "command": command.to_string(),
+                    "horizontal": position.horizontal,
+                    "depth": position.depth,
+                });
+                if let Some(aim) = aim {
+                    row["aim"] = json!(aim);
+                }
+                row.to_string()
+            }
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("{0} is not a trace format. Expected csv or json")]
+pub struct UnknownFormat(String);
+
+/// Statistics about a whole route. The extremes are paired with the index of the first command
+/// which reached them, and are `None` if there were no commands.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub commands: usize,
+    pub max_depth: Option<(i64, usize)>,
+    pub min_depth: Option<(i64, usize)>,
+    pub max_horizontal: Option<(i64, usize)>,
+
+    /// Where the submarine ended up
+    pub end: Position,
+}
+
+impl Summary {
+    pub fn add(&mut self, step: &Step) {
+        let Position { depth, horizontal } = step.position;
+
+        if self.max_depth.is_none_or(|(max, _)| depth > max) {
+            self.max_depth = Some((depth, step.index));
+        }
+        if self.min_depth.is_none_or(|(min, _)| depth < min) {
+            self.min_depth = Some((depth, step.index));
+        }
+        if self.max_horizontal.is_none_or(|(max, _)| horizontal > max) {
+            self.max_horizontal = Some((horizontal, step.index));
+        }
+
+        self.commands += 1;
+        self.end = step.position;
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let extreme = |name: &str, value: Option<(i64, usize)>| match value {
+            Some((value, index)) => format!("{}: {} (after command {})\n", name, value, index),
+            None => format!("{}: none\n", name),
+        };
+
+        writeln!(f, "commands: {}", self.commands)?;
+        f.write_str(&extreme("max depth", self.max_depth))?;
+        f.write_str(&extreme("min depth", self.min_depth))?;
+        f.write_str(&extreme("max horizontal", self.max_horizontal))?;
+        writeln!(
+            f,
+            "final position: horizontal {}, depth {}",
+            self.end.horizontal, self.end.depth
+        )?;
+        match self.end.solution() {
+            Ok(solution) => write!(f, "solution: {}", solution),
+            Err(error) => write!(f, "solution: none, {}", error),
+        }
+    }
+}
+
+/// Writes the trace of the commands to `writer` in the given format, returning the summary of
+/// the route. The steps up to a command which takes the submarine out of range are still written.
+pub fn write_trace<S: Solver, W: Write>(
+    solver: S,
+    commands: &[Command],
+    format: Format,
+    mut writer: W,
+) -> Result<Summary, TraceError> {
+    if let Some(header) = format.header(solver.aim().is_some()) {
+        writeln!(writer, "{}", header)?;
+    }
+
+    let mut summary = Summary::default();
+    for step in trace(solver, commands) {
+        let step = match step {
+            Ok(step) => step,
+            Err(error) => {
+                writer.flush()?;
+                return Err(error.into());
+            }
+        };
+
+        writeln!(writer, "{}", format.row(&step))?;
+        summary.add(&step);
+    }
+
+    writer.flush()?;
+    Ok(summary)
+}
+
+#[derive(Debug, Error)]
+pub enum TraceError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Solver(#[from] SolverError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_trace, Format, TraceError};
+    use crate::{command, Part1, Part2, SolverError};
+
+    const EXAMPLE: &str = "forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n";
+
+    #[test]
+    fn test_csv() {
+        let commands = command::parse(EXAMPLE).unwrap();
+        let mut output = Vec::new();
+        let summary = write_trace(Part2::default(), &commands, Format::Csv, &mut output).unwrap();
+
+        assert_eq!(
+            "index,command,horizontal,depth,aim
+0,forward 5,5,0,0
+1,down 5,5,0,5
+2,forward 8,13,40,5
+3,up 3,13,40,2
+4,down 8,13,40,10
+5,forward 2,15,60,10
+",
+            String::from_utf8(output).unwrap()
+        );
+
+        assert_eq!(6, summary.commands);
+        assert_eq!(Some((60, 5)), summary.max_depth);
+        assert_eq!(Some((0, 0)), summary.min_depth);
+        assert_eq!(Some((15, 5)), summary.max_horizontal);
+        assert_eq!(Ok(900), summary.end.solution());
+    }
+
+    #[test]
+    fn test_json() {
+        let commands = command::parse(EXAMPLE).unwrap();
+        let mut output = Vec::new();
+        let summary = write_trace(Part1::default(), &commands, Format::Json, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let first: serde_json::Value =
+            serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(
+            serde_json::json!({"index": 0, "command": "forward 5", "horizontal": 5, "depth": 0}),
+            first
+        );
+        assert_eq!(6, output.lines().count());
+
+        assert_eq!(Some((10, 4)), summary.max_depth);
+        assert_eq!(Ok(150), summary.end.solution());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let commands =
+            command::parse("set aim 2147483647\nrepeat 3 {\n    forward 2147483647\n}\n").unwrap();
+        let mut output = Vec::new();
+        let error = write_trace(Part2::default(), &commands, Format::Csv, &mut output).unwrap_err();
+
+        // the header, the aim and the first two moves made it out before the third went too deep
+        assert!(matches!(error, TraceError::Solver(SolverError::Overflow)));
+        assert_eq!(4, String::from_utf8(output).unwrap().lines().count());
+    }
+}
@@ -0,0 +1,31 @@
+use common::Day;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day03::{generate::generate, initial_sort, partition, Day03, Stat};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Finds both life support ratings by filtering the remaining numbers into new lists for every bit,
+/// as the solution originally did, and by partitioning one copy of the report in place, on
+/// generated reports of increasing size.
+fn ratings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day03/ratings");
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for size in [1_000, 100_000, 1_000_000] {
+        let report = Day03::parse(&generate(&mut rng, size)).expect("generated reports must parse");
+
+        group.bench_with_input(BenchmarkId::new("filter", size), &report, |b, report| {
+            b.iter(|| {
+                let (mut o2, mut co2) = initial_sort(&report.numbers, &report.count);
+                (o2.calc(), co2.calc())
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("partition", size), &report, |b, report| {
+            b.iter(|| partition::ratings(&report.numbers, report.width()))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, ratings);
+criterion_main!(benches);
@@ -0,0 +1,232 @@
+//! A step by step account of how the answers were reached, so that it's possible to check why a
+//! reading was chosen. An [Audit] has the gamma and epsilon rates, and both life support ratings
+//! with the [Step]s of filtering that led to them, and can be written as text or JSON.
+use std::{fmt::Display, str::FromStr};
+
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::{
+    part_1,
+    partition::{explain, Rating, Step},
+    Number, Report,
+};
+
+/// Everything worked out from a report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Audit {
+    /// How many bits wide the numbers are
+    pub width: usize,
+
+    /// How many numbers the report has
+    pub readings: usize,
+
+    pub gamma: Number,
+    pub epsilon: Number,
+    pub oxygen: Rating,
+    pub co2: Rating,
+}
+
+impl Audit {
+    pub fn new(report: &Report) -> Self {
+        let (gamma, epsilon) = part_1(&report.count, report.numbers.len());
+        let (oxygen, co2) = explain(&report.numbers, report.width());
+
+        Self {
+            width: report.width(),
+            readings: report.numbers.len(),
+            gamma,
+            epsilon,
+            oxygen,
+            co2,
+        }
+    }
+
+    /// The power consumption, or `None` if it's too big for a [Number].
+    pub fn power_consumption(&self) -> Option<Number> {
+        self.gamma.checked_mul(self.epsilon)
+    }
+
+    /// The life support rating, or `None` if either rating is missing or it's too big for a
+    /// [Number].
+    pub fn life_support(&self) -> Option<Number> {
+        self.oxygen.value?.checked_mul(self.co2.value?)
+    }
+
+    fn binary(&self, n: Number) -> String {
+        format!("{:0width$b}", n, width = self.width)
+    }
+
+    /// Which bit this is, counting from the left of the numbers as they're written in the report.
+    fn position(&self, step: &Step) -> usize {
+        self.width - 1 - step.bit
+    }
+
+    pub fn to_json(&self) -> Value {
+        let reading = |n: Option<Number>| match n {
+            Some(n) => json!({ "binary": self.binary(n), "decimal": decimal(n) }),
+            None => json!({ "binary": null, "decimal": null }),
+        };
+        let rating = |rating: &Rating| {
+            let mut value = reading(rating.value);
+            value["steps"] = rating
+                .steps
+                .iter()
+                .map(|step| {
+                    json!({
+                        "position": self.position(step),
+                        "candidates": step.candidates,
+                        "zeros": step.zeros,
+                        "ones": step.ones,
+                        "majority": step.majority().map(u8::from),
+                        "kept": u8::from(step.kept_ones),
+                        "tie_break": step.tied(),
+                    })
+                })
+                .collect();
+            value
+        };
+
+        json!({
+            "width": self.width,
+            "readings": self.readings,
+            "gamma": reading(Some(self.gamma)),
+            "epsilon": reading(Some(self.epsilon)),
+            "power_consumption": self.power_consumption().map(decimal),
+            "oxygen_generator": rating(&self.oxygen),
+            "co2_scrubber": rating(&self.co2),
+            "life_support": self.life_support().map(decimal),
+        })
+    }
+}
+
+/// JSON numbers are only read back reliably up to 64 bits, so wider numbers are written as strings.
+fn decimal(n: Number) -> Value {
+    u64::try_from(n).map_or_else(|_| json!(n.to_string()), |n| json!(n))
+}
+
+impl Display for Audit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reading = |n: Number| format!("{} ({})", self.binary(n), n);
+        let product = |n: Option<Number>| n.map_or_else(|| "none".to_string(), |n| n.to_string());
+
+        writeln!(f, "readings: {} of {} bits", self.readings, self.width)?;
+        writeln!(f, "gamma rate: {}", reading(self.gamma))?;
+        writeln!(f, "epsilon rate: {}", reading(self.epsilon))?;
+        writeln!(
+            f,
+            "power consumption: {}",
+            product(self.power_consumption())
+        )?;
+
+        for (name, rating) in [
+            ("oxygen generator rating", &self.oxygen),
+            ("co2 scrubber rating", &self.co2),
+        ] {
+            let value = rating.value.map_or_else(|| "none".to_string(), reading);
+            writeln!(f, "{}: {}", name, value)?;
+
+            for step in &rating.steps {
+                let kept = u8::from(step.kept_ones);
+                let choice = match step.majority() {
+                    Some(bit) => format!("majority {}, kept {}", u8::from(bit), kept),
+                    None => format!("tied, kept {} by tie-break", kept),
+                };
+                writeln!(
+                    f,
+                    "  position {}: {} candidates, {} zeros, {} ones, {} leaving {}",
+                    self.position(step),
+                    step.candidates,
+                    step.zeros,
+                    step.ones,
+                    choice,
+                    step.remaining(),
+                )?;
+            }
+        }
+
+        write!(f, "life support rating: {}", product(self.life_support()))
+    }
+}
+
+/// The ways an audit can be written out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    pub fn render(self, audit: &Audit) -> String {
+        match self {
+            Self::Text => audit.to_string(),
+            Self::Json => audit.to_json().to_string(),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("{0} is not an audit format. Expected text or json")]
+pub struct UnknownFormat(String);
+
+#[cfg(test)]
+mod tests {
+    use common::Day;
+    use serde_json::json;
+
+    use super::Audit;
+    use crate::Day03;
+
+    const EXAMPLE: &str = include_str!("../input/test.txt");
+
+    #[test]
+    fn test_example() {
+        let audit = Audit::new(&Day03::parse(EXAMPLE).unwrap());
+
+        assert_eq!(Some(198), audit.power_consumption());
+        assert_eq!(Some(230), audit.life_support());
+
+        let oxygen: Vec<_> = audit.oxygen.steps.iter().map(|s| s.remaining()).collect();
+        assert_eq!(vec![7, 4, 3, 2, 1], oxygen);
+        assert!(audit.oxygen.steps[4].tied());
+
+        let co2: Vec<_> = audit.co2.steps.iter().map(|s| s.remaining()).collect();
+        assert_eq!(vec![5, 2, 1], co2);
+        assert!(audit.co2.steps[2].tied());
+        assert!(!audit.co2.steps[2].kept_ones);
+    }
+
+    #[test]
+    fn test_json() {
+        let audit = Audit::new(&Day03::parse(EXAMPLE).unwrap()).to_json();
+
+        assert_eq!(json!({"binary": "10110", "decimal": 22}), audit["gamma"]);
+        assert_eq!(json!({"binary": "01001", "decimal": 9}), audit["epsilon"]);
+        assert_eq!("01010", audit["co2_scrubber"]["binary"]);
+        assert_eq!(
+            json!({
+                "position": 4,
+                "candidates": 2,
+                "zeros": 1,
+                "ones": 1,
+                "majority": null,
+                "kept": 1,
+                "tie_break": true,
+            }),
+            audit["oxygen_generator"]["steps"][4]
+        );
+        assert_eq!(230, audit["life_support"]);
+    }
+}
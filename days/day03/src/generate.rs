@@ -0,0 +1,40 @@
+use rand::{seq::index, Rng};
+
+use crate::{partition::ratings, Number};
+
+/// How many numbers the official input has.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// How wide the numbers in the official input are.
+pub const DEFAULT_WIDTH: usize = 12;
+
+/// Generates a diagnostic report of `size` distinct numbers. They're [DEFAULT_WIDTH] bits wide,
+/// unless there are too many numbers for that, in which case they're made wide enough to leave
+/// plenty of room. There are always at least 2 numbers, since a single number can't have both
+/// ratings.
+///
+/// Not every set of numbers has a CO2 scrubber rating, as filtering can leave no numbers behind
+/// when every remaining number has the same bit. Those reports are thrown away and tried again.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let size = size.max(2);
+    let width = DEFAULT_WIDTH.max((usize::BITS - size.leading_zeros()) as usize + 2);
+
+    loop {
+        let numbers: Vec<Number> = index::sample(rng, 1 << width, size)
+            .into_iter()
+            .map(|n| n as Number)
+            .collect();
+
+        if has_ratings(&numbers, width) {
+            return numbers
+                .iter()
+                .map(|n| format!("{:0width$b}\n", n, width = width))
+                .collect();
+        }
+    }
+}
+
+fn has_ratings(numbers: &[Number], width: usize) -> bool {
+    let (oxygen, co2) = ratings(numbers, width);
+    oxygen.is_some() && co2.is_some()
+}
@@ -0,0 +1,340 @@
+use std::{cmp::Ordering, str::FromStr};
+
+use bit_iter::BitIter;
+use color_eyre::eyre::eyre;
+use common::{Day, Diagnostic};
+
+pub mod audit;
+pub mod generate;
+pub mod partition;
+
+/// A number from the diagnostic report.
+pub type Number = u128;
+
+/// The widest numbers a report can have.
+pub const MAX_WIDTH: usize = Number::BITS as usize;
+
+/// The original way of finding the life support ratings, by filtering a list of the remaining
+/// numbers for each bit. [part_2] partitions the numbers in place instead (see [partition]), which
+/// saves building new lists for every bit, but this is kept around to benchmark against.
+pub trait Stat {
+    fn calc(&mut self) -> Number;
+}
+
+/// The numbers which could still be the oxygen generator rating, which are `width` bits wide.
+#[derive(Debug)]
+pub struct O2 {
+    pub numbers: Vec<Number>,
+    pub width: usize,
+}
+
+impl Stat for O2 {
+    fn calc(&mut self) -> Number {
+        strain(
+            &mut self.numbers,
+            self.width - 1,
+            &[Ordering::Greater, Ordering::Equal],
+        )
+        .expect("expects valid input")
+    }
+}
+
+/// The numbers which could still be the CO2 scrubber rating, which are `width` bits wide.
+#[derive(Debug)]
+pub struct CO2 {
+    pub numbers: Vec<Number>,
+    pub width: usize,
+}
+
+impl Stat for CO2 {
+    fn calc(&mut self) -> Number {
+        strain(&mut self.numbers, self.width - 1, &[Ordering::Less]).expect("expects valid input")
+    }
+}
+
+pub fn strain(data: &mut Vec<Number>, mut width: usize, comparison: &[Ordering]) -> Option<Number> {
+    while data.len() > 1 {
+        width -= 1;
+        let count = data.iter().filter(|&n| n & (1 << width) > 0).count();
+        let include = comparison.contains(&count.cmp(&data.len().div_ceil(2)));
+        data.retain(|&o| {
+            if include {
+                o & (1 << width) > 0
+            } else {
+                o & (1 << width) == 0
+            }
+        });
+    }
+
+    data.first().copied()
+}
+
+/// Parses a single line of the report as a binary number. The first line sets the `width` every
+/// other line has to match, and can be at most [MAX_WIDTH] bits.
+fn binary(line: &str, width: Option<usize>) -> Result<(Number, usize), Diagnostic> {
+    let start = line.len() - line.trim_start().len();
+    let digits = line.trim();
+
+    if let Some(i) = digits.find(|c| c != '0' && c != '1') {
+        return Err(Diagnostic::at_offset(line, start + i, "a binary digit"));
+    }
+    if digits.is_empty() {
+        return Err(Diagnostic::at_offset(line, start, "a binary number"));
+    }
+
+    match width {
+        Some(width) if digits.len() > width => {
+            return Err(Diagnostic::at_offset(
+                line,
+                start + width,
+                format!(
+                    "the end of the line after {} bits, as wide as the first line",
+                    width
+                ),
+            ))
+        }
+        Some(width) if digits.len() < width => {
+            return Err(Diagnostic::at_offset(
+                line,
+                start + digits.len(),
+                format!("{} bits, as wide as the first line", width),
+            ))
+        }
+        None if digits.len() > MAX_WIDTH => {
+            return Err(Diagnostic::at_offset(
+                line,
+                start + MAX_WIDTH,
+                format!("the end of the line after at most {} bits", MAX_WIDTH),
+            ))
+        }
+        _ => {}
+    }
+
+    let number = Number::from_str_radix(digits, 2).expect("only binary digits remain");
+    Ok((number, digits.len()))
+}
+
+pub fn part_1(count: &[usize], len: usize) -> (Number, Number) {
+    let gamma = count
+        .iter()
+        .enumerate()
+        .filter_map(|(index, &i)| (2 * i >= len).then_some(index))
+        .fold(0, |g: Number, i| g | (1 << i));
+
+    let mask = Number::MAX >> (MAX_WIDTH - count.len());
+    let epsilon = !gamma & mask;
+
+    (gamma, epsilon)
+}
+
+/// Finds the oxygen generator and CO2 scrubber ratings, or an error naming the bit the filtering
+/// ran out of candidates on.
+pub fn part_2(numbers: &[Number], width: usize) -> color_eyre::Result<(Number, Number)> {
+    let (oxygen, co2) = partition::explain(numbers, width);
+
+    Ok((
+        rating(oxygen, "oxygen generator")?,
+        rating(co2, "co2 scrubber")?,
+    ))
+}
+
+fn rating(rating: partition::Rating, name: &str) -> color_eyre::Result<Number> {
+    rating.value.ok_or_else(|| match rating.steps.last() {
+        Some(step) => eyre!("no {} candidate left at bit {}", name, step.bit),
+        None => eyre!("no {} candidate in an empty report", name),
+    })
+}
+
+pub fn initial_sort(numbers: &[Number], count: &[usize]) -> (O2, CO2) {
+    let mut o2 = Vec::new();
+    let mut co2 = Vec::new();
+
+    let last = *count.last().expect("a last should have been produced");
+    let initial_bit = last >= numbers.len().div_ceil(2);
+
+    for n in numbers {
+        let (l, r) = if initial_bit {
+            (&mut o2, &mut co2)
+        } else {
+            (&mut co2, &mut o2)
+        };
+        if n & (1 << (count.len() - 1)) > 0 {
+            l.push(*n);
+        } else {
+            r.push(*n);
+        }
+    }
+
+    let width = count.len();
+    (
+        O2 { numbers: o2, width },
+        CO2 {
+            numbers: co2,
+            width,
+        },
+    )
+}
+
+/// The diagnostic report, along with how many times each bit was set across all of the numbers.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub numbers: Vec<Number>,
+
+    /// How many numbers have each bit set, starting from the least significant bit. There's a count
+    /// for every bit, so this is as long as the numbers are wide.
+    pub count: Vec<usize>,
+}
+
+impl Report {
+    /// How many bits wide the numbers are.
+    pub fn width(&self) -> usize {
+        self.count.len()
+    }
+}
+
+impl FromStr for Report {
+    type Err = Diagnostic;
+
+    /// Reads the report, working out how wide the numbers are from the first line.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut numbers = Vec::new();
+        let mut count = Vec::new();
+
+        for (i, line) in input.lines().enumerate() {
+            let width = (i > 0).then_some(count.len());
+            let (number, width) = binary(line, width).map_err(|e| e.below(i))?;
+
+            count.resize(width, 0);
+            for index in BitIter::from(number) {
+                count[index] += 1;
+            }
+            numbers.push(number);
+        }
+
+        if numbers.is_empty() {
+            return Err(Diagnostic::at_offset(input, 0, "a binary number"));
+        }
+
+        Ok(Self { numbers, count })
+    }
+}
+
+pub struct Day03;
+
+impl Day for Day03 {
+    const DAY: usize = 3;
+
+    type Input = Report;
+    type Part1 = Number;
+    type Part2 = Number;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(input.parse()?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        let (gamma, epsilon) = part_1(&input.count, input.numbers.len());
+        gamma
+            .checked_mul(epsilon)
+            .ok_or_else(|| eyre!("the power consumption doesn't fit in {} bits", MAX_WIDTH))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        let (oxygen, co2) = part_2(&input.numbers, input.width())?;
+        oxygen
+            .checked_mul(co2)
+            .ok_or_else(|| eyre!("the life support rating doesn't fit in {} bits", MAX_WIDTH))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common::Day;
+
+    use super::{initial_sort, part_1, part_2, Day03, Number, Report, Stat};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::generate::generate;
+
+    const EXAMPLE: &str = include_str!("../input/test.txt");
+
+    #[test]
+    fn test_example() {
+        let report = Day03::parse(EXAMPLE).unwrap();
+
+        assert_eq!(5, report.width());
+        assert_eq!(12, report.numbers.len());
+        assert_eq!(198, Day03::part_1(&report).unwrap());
+        assert_eq!(230, Day03::part_2(&report).unwrap());
+    }
+
+    #[test]
+    fn test_wide_numbers() {
+        // every number from the example repeated 16 times over, so filtering on the first 5 bits
+        // picks out the same ratings as the example
+        let input: String = EXAMPLE
+            .lines()
+            .map(|line| format!("{}\n", line.repeat(16)))
+            .collect();
+        let report = Day03::parse(&input).unwrap();
+        let repeat = |n: Number| (0..16).fold(0, |acc, _| acc << 5 | n);
+
+        assert_eq!(80, report.width());
+        assert_eq!(
+            (repeat(0b10110), repeat(0b01001)),
+            part_1(&report.count, report.numbers.len())
+        );
+        assert_eq!(
+            (repeat(0b10111), repeat(0b01010)),
+            part_2(&report.numbers, report.width()).unwrap()
+        );
+
+        // the answers need 160 bits, which is too many
+        assert!(Day03::part_1(&report).is_err());
+    }
+
+    #[test]
+    fn test_odd_length() {
+        // 2 of the 5 numbers have the first bit set, which isn't most of them
+        let report = Day03::parse("10\n11\n01\n01\n01\n").unwrap();
+
+        assert_eq!((0b01, 0b10), part_1(&report.count, report.numbers.len()));
+        assert_eq!(2, Day03::part_1(&report).unwrap());
+    }
+
+    #[test]
+    fn test_matches_filtering() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for size in [2, 3, 10, 100, 1000] {
+            let report = Day03::parse(&generate(&mut rng, size)).unwrap();
+            let (mut o2, mut co2) = initial_sort(&report.numbers, &report.count);
+
+            assert_eq!(
+                (o2.calc(), co2.calc()),
+                part_2(&report.numbers, report.width()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_no_candidates_left() {
+        // every number starts with a 1, so the co2 scrubber rating keeps the 0s and there are none
+        let report = Day03::parse("10\n11\n11\n").unwrap();
+        let error = Day03::part_2(&report).unwrap_err();
+
+        assert_eq!("no co2 scrubber candidate left at bit 1", error.to_string());
+    }
+
+    #[test]
+    fn test_ragged_lines() {
+        let error = "00100\n1111\n".parse::<Report>().unwrap_err();
+        assert_eq!((2, 5), (error.line, error.column));
+
+        let error = "00100\n111100\n".parse::<Report>().unwrap_err();
+        assert_eq!((2, 6), (error.line, error.column));
+
+        assert!("".parse::<Report>().is_err());
+        assert!("1".repeat(129).parse::<Report>().is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Finding the life support ratings without building new lists of numbers for every bit. The
+//! report is copied once, and each step of the filtering partitions the remaining numbers in place,
+//! moving the ones with a 0 in the current bit in front of the ones with a 1, so the numbers which
+//! are kept are always one slice of the copy.
+//!
+//! After the first bit the two ratings keep different sides of the partition, so they can carry on
+//! filtering the same copy without getting in each other's way.
+//!
+//! Each rating also records the [Step]s it took, so the choice of a reading can be explained.
+use std::cmp::Ordering;
+
+use crate::Number;
+
+/// What happened when filtering the candidates for a rating on one bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    /// The bit which was filtered on, counting from the least significant bit
+    pub bit: usize,
+
+    /// How many numbers were still candidates before filtering on this bit
+    pub candidates: usize,
+    pub zeros: usize,
+    pub ones: usize,
+
+    /// Whether the numbers with a 1 in this bit were kept
+    pub kept_ones: bool,
+}
+
+impl Step {
+    /// Whether the most common bit is 1, or `None` if there were as many 0s as 1s.
+    pub fn majority(&self) -> Option<bool> {
+        match self.ones.cmp(&self.zeros) {
+            Ordering::Less => Some(false),
+            Ordering::Equal => None,
+            Ordering::Greater => Some(true),
+        }
+    }
+
+    /// Whether there was a tie, leaving the rating's tie-break to decide which bit to keep.
+    pub fn tied(&self) -> bool {
+        self.majority().is_none()
+    }
+
+    /// How many numbers are left after filtering on this bit.
+    pub fn remaining(&self) -> usize {
+        if self.kept_ones {
+            self.ones
+        } else {
+            self.zeros
+        }
+    }
+}
+
+/// A life support rating, along with the steps taken to find it. There are no steps when the
+/// report only has one number, since there's nothing to filter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rating {
+    /// The rating, or `None` if the filtering left nothing behind
+    pub value: Option<Number>,
+    pub steps: Vec<Step>,
+}
+
+/// Finds the oxygen generator and CO2 scrubber ratings of the `width` bit wide numbers. Either
+/// rating is `None` if the filtering leaves nothing behind, which happens to the CO2 scrubber rating
+/// when every remaining number has the same bit.
+pub fn ratings(numbers: &[Number], width: usize) -> (Option<Number>, Option<Number>) {
+    let (oxygen, co2) = explain(numbers, width);
+    (oxygen.value, co2.value)
+}
+
+/// Like [ratings], but keeps the steps taken to find the oxygen generator and CO2 scrubber ratings.
+pub fn explain(numbers: &[Number], width: usize) -> (Rating, Rating) {
+    if numbers.len() <= 1 {
+        let only = Rating {
+            value: numbers.first().copied(),
+            steps: Vec::new(),
+        };
+        return (only.clone(), only);
+    }
+
+    let mut numbers = numbers.to_vec();
+    let candidates = numbers.len();
+    let bit = width - 1;
+    let (zeros, ones) = partition(&mut numbers, bit);
+
+    let step = Step {
+        bit,
+        candidates,
+        zeros: zeros.len(),
+        ones: ones.len(),
+        kept_ones: oxygen(zeros.len(), ones.len()),
+    };
+
+    let (for_oxygen, for_co2) = if step.kept_ones {
+        (ones, zeros)
+    } else {
+        (zeros, ones)
+    };
+    let co2_step = Step {
+        kept_ones: co2(step.zeros, step.ones),
+        ..step
+    };
+
+    (
+        rating(for_oxygen, bit, oxygen, step),
+        rating(for_co2, bit, co2, co2_step),
+    )
+}
+
+/// The oxygen generator rating keeps the most common bit, or 1 on a tie. Given how many numbers
+/// have a 0 and a 1 in the current bit, this is whether to keep the 1s.
+fn oxygen(zeros: usize, ones: usize) -> bool {
+    ones >= zeros
+}
+
+/// The CO2 scrubber rating keeps the least common bit, or 0 on a tie.
+fn co2(zeros: usize, ones: usize) -> bool {
+    ones < zeros
+}
+
+/// Carries on filtering `candidates`, which have already been filtered on the bits above `bit`,
+/// until there's one left. `first` is the step which filtered on `bit` itself.
+fn rating(
+    mut candidates: &mut [Number],
+    bit: usize,
+    keep_ones: fn(usize, usize) -> bool,
+    first: Step,
+) -> Rating {
+    let mut steps = vec![first];
+    for bit in (0..bit).rev() {
+        if candidates.len() <= 1 {
+            break;
+        }
+
+        let count = candidates.len();
+        let (zeros, ones) = partition(candidates, bit);
+        let step = Step {
+            bit,
+            candidates: count,
+            zeros: zeros.len(),
+            ones: ones.len(),
+            kept_ones: keep_ones(zeros.len(), ones.len()),
+        };
+
+        steps.push(step);
+        candidates = if step.kept_ones { ones } else { zeros };
+    }
+
+    Rating {
+        value: candidates.first().copied(),
+        steps,
+    }
+}
+
+/// Moves the numbers with a 0 in `bit` in front of the numbers with a 1, returning the two halves.
+fn partition(numbers: &mut [Number], bit: usize) -> (&mut [Number], &mut [Number]) {
+    // everything between split and i has a 1, so swapping unconditionally is harmless, and saves
+    // a branch which is as likely to go either way
+    let mut split = 0;
+    for i in 0..numbers.len() {
+        let zero = numbers[i] & (1 << bit) == 0;
+        numbers.swap(split, i);
+        split += usize::from(zero);
+    }
+
+    numbers.split_at_mut(split)
+}
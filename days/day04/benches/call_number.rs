@@ -0,0 +1,103 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use day04::{
+    index::Index,
+    parser::{self, Parse},
+};
+
+const EXAMPLE: &str = include_str!("../input/test.txt");
+
+/// Repeats every board so the index has `copies` times as many boards to track.
+fn scale(parse: &Parse, copies: usize) -> Parse {
+    Parse {
+        numbers: parse.numbers.clone(),
+        boards: std::iter::repeat_n(&parse.boards, copies)
+            .flatten()
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Moves every number up past 127, so the index has to count the numbers left in each row and
+/// column rather than keeping a mask of them.
+fn widen(parse: &Parse) -> Parse {
+    let widen = |n: &u32| n + 1000;
+    Parse {
+        numbers: parse.numbers.iter().map(widen).collect(),
+        boards: parse
+            .boards
+            .iter()
+            .map(|board| {
+                board
+                    .iter()
+                    .map(|row| row.iter().map(widen).collect())
+                    .collect()
+            })
+            .collect(),
+    }
+}
+
+/// Calls every number, which plays the whole game until the last board has won.
+fn call_all(c: &mut Criterion) {
+    let example = parser::parse(EXAMPLE).expect("example must parse");
+    let mut group = c.benchmark_group("day04/call_number");
+
+    for (name, parse) in [
+        ("mask", scale(&example, 1)),
+        ("mask", scale(&example, 100)),
+        ("count", widen(&scale(&example, 100))),
+    ] {
+        group.bench_with_input(
+            BenchmarkId::new(name, parse.boards.len()),
+            &parse,
+            |b, parse| {
+                b.iter_batched(
+                    || Index::from(parse.boards.clone()),
+                    |mut index| {
+                        for &n in &parse.numbers {
+                            black_box(index.call_number(n));
+                        }
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Getting the index ready for another game after playing one, by indexing the boards again or by
+/// resetting the index.
+fn start_again(c: &mut Criterion) {
+    let parse = scale(&parser::parse(EXAMPLE).expect("example must parse"), 100);
+    let mut group = c.benchmark_group("day04/start_again");
+
+    group.bench_function("rebuild", |b| {
+        b.iter(|| black_box(Index::from(parse.boards.clone())))
+    });
+
+    let index = Index::from(parse.boards.clone());
+    group.bench_function("reset", |b| {
+        b.iter_batched(
+            || {
+                let mut played = index.clone();
+                for &n in &parse.numbers {
+                    played.call_number(n);
+                }
+                played
+            },
+            |mut played| {
+                played.reset();
+                played
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, call_all, start_again);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+use rand::{
+    seq::{index, SliceRandom},
+    Rng,
+};
+
+/// How many boards the official input has.
+pub const DEFAULT_SIZE: usize = 100;
+
+const NUMBERS: usize = 100;
+const BOARD_SIZE: usize = 5;
+
+/// Generates `size` boards of distinct numbers, and draws every number once in a random order
+/// so that every board eventually wins.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut draws: Vec<usize> = (0..NUMBERS).collect();
+    draws.shuffle(rng);
+
+    let mut out = draws
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    out.push('\n');
+
+    for _ in 0..size {
+        let numbers = index::sample(rng, NUMBERS, BOARD_SIZE * BOARD_SIZE).into_vec();
+
+        out.push('\n');
+        for row in numbers.chunks(BOARD_SIZE) {
+            let row: Vec<_> = row.iter().map(|n| format!("{:>2}", n)).collect();
+            out.push_str(&row.join(" "));
+            out.push('\n');
+        }
+    }
+
+    out
+}
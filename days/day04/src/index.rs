@@ -1,44 +1,83 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 
 use bit_iter::BitIter;
 use slotmap::{DefaultKey, SlotMap};
 
-use crate::parser::{Board, Boards, LineOfNumbers};
+use crate::{
+    parser::{Board, Boards, LineOfNumbers, Number, Shape},
+    pattern::{Pattern, PatternError},
+};
 
+/// The numbers in a line which haven't been called yet.
 #[derive(Clone, Copy)]
-pub enum Span {
-    Row(u128),
-    Col(u128),
-    Board(usize, usize),
+pub enum Cells {
+    /// Which numbers are left, as bits of a mask. This is the fast path, which only works when
+    /// every number on the boards is below 128.
+    Mask(u128),
+
+    /// How many numbers are left, for boards with bigger numbers. This relies on each number only
+    /// being called once, which [Index::call_number] makes sure of.
+    Count(usize),
+}
+
+impl Cells {
+    /// The cells of an empty line, for boards whose largest number is `max`.
+    fn empty(max: Number) -> Self {
+        if max < u128::BITS {
+            Self::Mask(0)
+        } else {
+            Self::Count(0)
+        }
+    }
+
+    fn insert(&mut self, num: Number) {
+        match self {
+            Self::Mask(x) => *x |= 1 << num,
+            Self::Count(x) => *x += 1,
+        }
+    }
+
+    fn call_number(&mut self, num: Number) {
+        match self {
+            Self::Mask(x) => *x &= !(1 << num),
+            Self::Count(x) => *x -= 1,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Self::Mask(x) => *x == 0,
+            Self::Count(x) => *x == 0,
+        }
+    }
 }
 
-impl std::fmt::Debug for Span {
+impl std::fmt::Debug for Cells {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Row(arg0) => {
-                let mut d = f.debug_tuple("Row");
-                for b in BitIter::from(*arg0) {
-                    d.field(&b);
-                }
-                d.finish()
-            }
-            Self::Col(arg0) => {
-                let mut d = f.debug_tuple("Col");
-                for b in BitIter::from(*arg0) {
-                    d.field(&b);
-                }
-                d.finish()
-            }
-            Self::Board(arg0, arg1) => f.debug_tuple("Board").field(arg0).field(arg1).finish(),
+            Self::Mask(x) => f.debug_set().entries(BitIter::from(*x)).finish(),
+            Self::Count(x) => write!(f, "{} left", x),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum Span {
+    /// A set of cells which wins the board once they've all been called, from the pattern at this
+    /// index
+    Line(usize, Cells),
+
+    /// The board at this index, with the sum of its numbers which haven't been called
+    Board(usize, usize),
+}
+
 impl Span {
-    fn insert(&mut self, num: u8) {
+    fn insert(&mut self, num: Number) {
         match self {
-            Span::Row(x) => *x |= 1 << num,
-            Span::Col(x) => *x |= 1 << num,
+            Span::Line(_, x) => x.insert(num),
             Span::Board(_, x) => *x += num as usize,
         }
     }
@@ -52,52 +91,116 @@ impl Span {
 
     pub fn is_complete(&self) -> bool {
         match self {
-            Span::Col(x) | Span::Row(x) => x.count_ones() == 0,
+            Span::Line(_, x) => x.is_empty(),
             _ => false,
         }
     }
 
-    pub fn call_number(&mut self, num: u8) -> bool {
+    pub fn call_number(&mut self, num: Number) -> bool {
         match self {
-            Span::Row(x) => *x &= !(1 << num),
-            Span::Col(x) => *x &= !(1 << num),
+            Span::Line(_, x) => x.call_number(num),
             Span::Board(_, x) => *x -= num as usize,
         }
         self.is_complete()
     }
 }
 
+/// A board winning with one or more of the patterns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Win {
+    pub board: usize,
+
+    /// The indexes of the patterns the board completed with the winning number, out of the patterns
+    /// the index was made with. These are in order, and there's more than one when the number
+    /// finished several patterns at once.
+    pub patterns: Vec<usize>,
+
+    /// The sum of the numbers on the board which haven't been called, times the number just called
+    pub score: usize,
+}
+
+/// A number on a board, with the lines it's part of. The keys of the lines are kept together in
+/// one list by the index, to save a trip to another allocation for each entry.
 #[derive(Debug, Clone)]
 pub struct Entry {
-    row: DefaultKey,
-    col: DefaultKey,
+    lines: Range<usize>,
     board: DefaultKey,
 }
 
-#[derive(Debug, Default)]
+/// Keeps track of every board as numbers are called. Only the values of the spans, the boards
+/// which have won and the numbers which have been called change as the game goes on. The spans
+/// keep their keys, so [Index::reset] can start a new game by writing the starting values back
+/// over them, without indexing the boards again.
+#[derive(Debug, Clone)]
 pub struct Index {
-    cleanup_spans: HashMap<usize, Vec<DefaultKey>>,
-    cleanup_entries: HashMap<usize, Vec<DefaultKey>>,
+    /// What an empty line starts out as, which depends on how big the numbers are
+    empty: Cells,
+    patterns: Vec<Pattern>,
     spans: SlotMap<DefaultKey, Span>,
     entries: SlotMap<DefaultKey, Entry>,
-    numbers: HashMap<u8, Vec<DefaultKey>>,
+    lines: Vec<DefaultKey>,
+    numbers: HashMap<Number, Vec<DefaultKey>>,
+
+    /// The values of the spans before any numbers were called, in the order the spans iterate in
+    start: Vec<Span>,
+
+    /// Which boards have won, and stopped playing
+    won: Vec<bool>,
+    called: HashSet<Number>,
 }
 
 impl Index {
-    pub fn call_number(&mut self, num: u8) -> Option<Vec<(usize, usize)>> {
-        if let Some(winners) = self.inner_call_number(num) {
-            for (i, _) in winners.iter() {
-                if let Some(spans) = self.cleanup_spans.remove(i) {
-                    for s in spans.into_iter() {
-                        self.spans.remove(s);
-                    }
-                }
+    /// Indexes the boards, which win with any of the patterns. Fails if a pattern doesn't fit a
+    /// board, like a diagonal on a board which isn't square.
+    pub fn with_patterns(boards: Boards, patterns: Vec<Pattern>) -> Result<Self, PatternError> {
+        let max = boards.iter().flatten().flatten().copied().max();
+        let mut index = Self {
+            empty: Cells::empty(max.unwrap_or(0)),
+            patterns,
+            spans: SlotMap::new(),
+            entries: SlotMap::new(),
+            lines: Vec::new(),
+            numbers: HashMap::new(),
+            start: Vec::new(),
+            won: vec![false; boards.len()],
+            called: HashSet::new(),
+        };
 
-                if let Some(entries) = self.cleanup_entries.remove(i) {
-                    for e in entries.into_iter() {
-                        self.entries.remove(e);
-                    }
-                }
+        for (i, board) in boards.into_iter().enumerate() {
+            index.insert_board(i, board)?;
+        }
+        index.start = index.spans.values().copied().collect();
+
+        Ok(index)
+    }
+
+    /// Puts every board back the way it was before any numbers were called, overwriting the spans
+    /// in place so nothing is allocated.
+    pub fn reset(&mut self) {
+        for (span, start) in self.spans.values_mut().zip(&self.start) {
+            *span = *start;
+        }
+        self.won.fill(false);
+        self.called.clear();
+    }
+
+    /// How many boards there are, including the ones which have already won.
+    pub fn boards(&self) -> usize {
+        self.won.len()
+    }
+
+    /// The patterns the boards can win with, which [Win::patterns] indexes into.
+    pub fn patterns(&self) -> &[Pattern] {
+        &self.patterns
+    }
+
+    /// Marks the number on every board, returning each board which won and the patterns it won
+    /// with. A board which wins with more than one pattern at once still only wins once, with all
+    /// of them. Boards stop playing once they've won.
+    pub fn call_number(&mut self, num: Number) -> Option<Vec<Win>> {
+        if let Some(winners) = self.inner_call_number(num) {
+            for Win { board, .. } in winners.iter() {
+                self.won[*board] = true;
             }
 
             return Some(winners);
@@ -106,91 +209,109 @@ impl Index {
         None
     }
 
-    fn inner_call_number(&mut self, num: u8) -> Option<Vec<(usize, usize)>> {
-        let mut winners = Vec::new();
+    /// Marks the number on every board it's on. Each number is only marked once, however many times
+    /// it's called.
+    fn inner_call_number(&mut self, num: Number) -> Option<Vec<Win>> {
+        let mut completed = Vec::new();
+        if !self.called.insert(num) {
+            return None;
+        }
+
         if let Some(entries) = self.numbers.get(&num) {
             for entry_key in entries.iter() {
-                if let Some([row, col, board]) = self
-                    .entries
-                    .get(*entry_key)
-                    .and_then(|e| self.spans.get_disjoint_mut([e.row, e.col, e.board]))
-                {
-                    board.call_number(num);
-                    let row_done = row.call_number(num);
-                    let col_done = col.call_number(num);
-                    if row_done || col_done {
-                        if let Some(winner) = board.score().map(|(i, s)| (i, s * num as usize)) {
-                            winners.push(winner);
+                let entry = match self.entries.get(*entry_key) {
+                    Some(entry) => entry,
+                    None => continue,
+                };
+
+                // boards which have already won aren't marked any more
+                match self.spans.get_mut(entry.board) {
+                    Some(Span::Board(i, _)) if self.won[*i] => continue,
+                    Some(board) => board.call_number(num),
+                    None => continue,
+                };
+                for &line in &self.lines[entry.lines.clone()] {
+                    if let Some(Span::Line(pattern, cells)) = self.spans.get_mut(line) {
+                        cells.call_number(num);
+                        if cells.is_empty() {
+                            completed.push((entry.board, *pattern));
                         }
                     }
                 }
             }
         }
 
-        (!winners.is_empty()).then(|| winners)
-    }
+        // the scores are worked out once the number has been marked everywhere, in case it's on a
+        // board more than once. Sorting also brings the patterns each board completed together.
+        completed.sort_unstable();
+        completed.dedup();
+        let mut winners: Vec<Win> = Vec::new();
+        for (board, pattern) in completed {
+            let (board, score) = match self.spans.get(board).and_then(Span::score) {
+                Some(score) => score,
+                None => continue,
+            };
 
-    fn insert_board(&mut self, i: usize, board: Board) {
-        let mut board_span = Span::Board(i, 0);
-        let mut rows = [Span::Row(0); 5];
-        let mut cols = [Span::Col(0); 5];
+            match winners.last_mut() {
+                Some(win) if win.board == board => win.patterns.push(pattern),
+                _ => winners.push(Win {
+                    board,
+                    patterns: vec![pattern],
+                    score: score * num as usize,
+                }),
+            }
+        }
 
-        Self::populate_spans(&mut board_span, &mut rows, &mut cols, &board);
+        (!winners.is_empty()).then_some(winners)
+    }
 
-        let mut row_keys = [DefaultKey::default(); 5];
-        let mut col_keys = [DefaultKey::default(); 5];
-        let board_key = self.insert_span(i, board_span);
+    fn insert_board(&mut self, i: usize, board: Board) -> Result<(), PatternError> {
+        let shape = Shape::of(&board);
 
-        self.insert_spans(i, &mut row_keys, &rows);
-        self.insert_spans(i, &mut col_keys, &cols);
-        self.insert_entries(i, board_key, &row_keys, &col_keys, &board);
-    }
+        let mut board_span = Span::Board(i, 0);
+        for num in board.iter().flatten() {
+            board_span.insert(*num);
+        }
+        let board_key = self.spans.insert(board_span);
 
-    fn populate_spans(
-        board_span: &mut Span,
-        rows: &mut [Span],
-        cols: &mut [Span],
-        board: &[LineOfNumbers],
-    ) {
-        for (row, board_row) in rows.iter_mut().zip(board.iter()) {
-            for (col, num) in cols.iter_mut().zip(board_row.iter()) {
-                row.insert(*num);
-                col.insert(*num);
-                board_span.insert(*num);
-            }
+        let mut lines = Vec::new();
+        for (pattern, cells) in self.patterns.iter().enumerate() {
+            lines.extend(cells.lines(shape)?.into_iter().map(|line| (pattern, line)));
         }
-    }
 
-    fn insert_spans(&mut self, i: usize, keys: &mut [DefaultKey], spans: &[Span]) {
-        for (span, slot) in spans.iter().zip(keys.iter_mut()) {
-            *slot = self.insert_span(i, *span);
+        // the lines each cell is part of, laid out like the board
+        let mut cells = vec![vec![Vec::new(); shape.cols]; shape.rows];
+        for (pattern, line) in lines {
+            let mut span = Span::Line(pattern, self.empty);
+            for &(row, col) in line.iter() {
+                span.insert(board[row][col]);
+            }
+
+            let key = self.spans.insert(span);
+            for (row, col) in line {
+                cells[row][col].push(key);
+            }
         }
-    }
 
-    fn insert_span(&mut self, i: usize, span: Span) -> DefaultKey {
-        let key = self.spans.insert(span);
-        self.cleanup_spans.entry(i).or_default().push(key);
-        key
+        self.insert_entries(board_key, cells, &board);
+        Ok(())
     }
 
     fn insert_entries(
         &mut self,
-        i: usize,
         board_key: DefaultKey,
-        rows: &[DefaultKey],
-        cols: &[DefaultKey],
+        cells: Vec<Vec<Vec<DefaultKey>>>,
         board: &[LineOfNumbers],
     ) {
-        let entry_cleanup_list = self.cleanup_entries.entry(i).or_default();
-        for (row, board_row) in rows.iter().copied().zip(board.iter()) {
-            for (col, num) in cols.iter().copied().zip(board_row.iter()) {
+        for (row, board_row) in cells.into_iter().zip(board.iter()) {
+            for (lines, num) in row.into_iter().zip(board_row.iter()) {
+                let start = self.lines.len();
+                self.lines.extend(lines);
                 let entry_key = self.entries.insert(Entry {
-                    row,
-                    col,
+                    lines: start..self.lines.len(),
                     board: board_key,
                 });
 
-                entry_cleanup_list.push(entry_key);
                 self.numbers.entry(*num).or_default().push(entry_key);
             }
         }
@@ -198,13 +319,9 @@ impl Index {
 }
 
 impl From<Boards> for Index {
+    /// Indexes boards which win with a row or column, like the original game.
     fn from(boards: Boards) -> Self {
-        let mut index = Self::default();
-
-        for (i, board) in boards.into_iter().enumerate() {
-            index.insert_board(i, board);
-        }
-
-        index
+        Self::with_patterns(boards, Pattern::DEFAULT.to_vec())
+            .expect("rows and columns fit any board")
     }
 }
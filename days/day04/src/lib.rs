@@ -0,0 +1,145 @@
+use color_eyre::eyre::eyre;
+use common::Day;
+use index::{Index, Win};
+use parser::{Number, Parse};
+
+pub mod generate;
+pub mod index;
+pub mod parser;
+pub mod pattern;
+pub mod simulate;
+
+/// Calls every number in order and yields each win in the order the boards won, with boards
+/// winning by a row or column.
+pub fn winners(parse: &Parse) -> impl Iterator<Item = Win> + '_ {
+    play(&parse.numbers, parse.boards.clone().into())
+}
+
+/// Calls every number in order on the boards in the index, yielding each win as it happens.
+pub fn play(numbers: &[Number], mut index: Index) -> impl Iterator<Item = Win> + '_ {
+    numbers
+        .iter()
+        .filter_map(move |&n| index.call_number(n))
+        .flat_map(|w| w.into_iter())
+}
+
+pub struct Day04;
+
+impl Day for Day04 {
+    const DAY: usize = 4;
+
+    type Input = Parse;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(parser::parse(input)?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        winners(input)
+            .next()
+            .map(|win| win.score)
+            .ok_or_else(|| eyre!("no board won"))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        winners(input)
+            .last()
+            .map(|win| win.score)
+            .ok_or_else(|| eyre!("no board won"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        index::{Index, Win},
+        parser::{self, Parse},
+        pattern::Pattern,
+        play, winners,
+    };
+
+    const EXAMPLE: &str = include_str!("../input/test.txt");
+
+    #[test]
+    fn test_rectangular_boards() {
+        // the first board completes its second column on 5, and the second its first row on 8
+        let input = "2,5,9,7,8\n\n1 2 3\n4 5 6\n\n7 8 9\n10 11 12\n";
+        let parse = parser::parse(input).unwrap();
+
+        let winners: Vec<_> = winners(&parse).map(|w| (w.board, w.score)).collect();
+        assert_eq!(vec![(0, 14 * 5), (1, 33 * 8)], winners);
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let example = parser::parse(EXAMPLE).unwrap();
+        let shift = |n: &u32| n + 1000;
+        let shifted = Parse {
+            numbers: example.numbers.iter().map(shift).collect(),
+            boards: example
+                .boards
+                .iter()
+                .map(|board| {
+                    board
+                        .iter()
+                        .map(|row| row.iter().map(shift).collect())
+                        .collect()
+                })
+                .collect(),
+        };
+
+        let order = |parse| winners(parse).map(|win| win.board).collect::<Vec<_>>();
+        assert_eq!(order(&example), order(&shifted));
+
+        // the third board wins on 24 with 13 numbers left, which sum to 188 before shifting
+        let win = winners(&shifted).next().unwrap();
+        assert_eq!((188 + 13 * 1000) * 1024, win.score);
+    }
+
+    #[test]
+    fn test_patterns() {
+        // 5 finishes both diagonals and the X on the first board at once, which is one win with
+        // both patterns, then 2 finishes the second board's first row
+        let input = "1,9,3,7,5,2,4\n\n1 2 3\n4 5 6\n7 8 9\n\n3 2 1\n9 8 7\n6 5 4\n";
+        let parse = parser::parse(input).unwrap();
+        let patterns = vec![
+            Pattern::Row,
+            Pattern::Diagonal,
+            "#.#/.#./#.#".parse().unwrap(),
+        ];
+        let index = Index::with_patterns(parse.boards, patterns).unwrap();
+
+        let wins: Vec<_> = play(&parse.numbers, index)
+            .map(
+                |Win {
+                     board,
+                     patterns,
+                     score,
+                 }| (board, patterns, score),
+            )
+            .collect();
+        assert_eq!(vec![(0, vec![1, 2], 20 * 5), (1, vec![0], 18 * 2)], wins);
+    }
+
+    #[test]
+    fn test_reset() {
+        let parse = parser::parse(EXAMPLE).unwrap();
+        let mut index = Index::from(parse.boards);
+
+        let game = |index: &mut Index| -> Vec<_> {
+            parse
+                .numbers
+                .iter()
+                .filter_map(|&n| index.call_number(n))
+                .flatten()
+                .collect()
+        };
+
+        let first = game(&mut index);
+        assert!(game(&mut index).is_empty());
+        index.reset();
+        assert_eq!(first, game(&mut index));
+    }
+}
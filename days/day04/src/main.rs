@@ -1,36 +1,111 @@
-use std::{fs::File, io::Read, path::PathBuf};
+use std::path::PathBuf;
 
-use index::Index;
+use color_eyre::eyre::eyre;
+use common::input;
+use day04::{
+    index::Index,
+    parser::{self, Shape},
+    pattern::Pattern,
+    play,
+    simulate::simulate,
+};
 use structopt::StructOpt;
 
-mod index;
-mod parser;
-
 #[derive(StructOpt)]
 #[structopt(name = "aoc2021-day-4", about = "The fourth day of advent of code")]
 struct Cli {
-    #[structopt(parse(from_os_str), help = "the input file from adventofcode")]
-    input: PathBuf,
+    #[structopt(
+        parse(from_os_str),
+        help = "the input file from adventofcode. Reads from stdin if omitted or `-`"
+    )]
+    input: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        help = "how many rows and columns the boards must have, like 5x5. Taken from the first board if omitted"
+    )]
+    shape: Option<Shape>,
+
+    #[structopt(
+        long = "pattern",
+        number_of_values = 1,
+        help = "a way for boards to win: row, column, diagonal, corners, blackout, or a mask like #.#/.#./#.# with a row for each row of the boards. Can be given more than once. Defaults to rows and columns"
+    )]
+    patterns: Vec<Pattern>,
+
+    /// Plays the game this many times with the numbers drawn in a random order, and prints how
+    /// likely each board is to win first or last and when it usually wins, rather than playing
+    /// the game once.
+    #[structopt(
+        long,
+        help = "play this many games with the numbers shuffled, and print the odds of each board winning first or last"
+    )]
+    simulate: Option<usize>,
+
+    #[structopt(
+        long,
+        default_value = "0",
+        help = "the seed for shuffling the numbers when simulating. The same seed always gives the same results"
+    )]
+    seed: u64,
 }
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let opts = Cli::from_args();
-    let mut buf = String::new();
+    let buf = input::read_to_string(opts.input.as_deref())?;
+
+    let parse = match opts.shape {
+        Some(shape) => parser::parse_shaped(&buf, shape)?,
+        None => parser::parse(&buf)?,
+    };
 
-    File::open(opts.input)?.read_to_string(&mut buf)?;
+    let patterns = if opts.patterns.is_empty() {
+        Pattern::DEFAULT.to_vec()
+    } else {
+        opts.patterns
+    };
+    let index = Index::with_patterns(parse.boards, patterns.clone())?;
 
-    let parser::Parse { numbers, boards } = parser::parse(&buf)?;
+    if let Some(runs) = opts.simulate {
+        if runs == 0 {
+            return Err(eyre!("--simulate needs at least one game to play"));
+        }
 
-    let mut index: Index = boards.into();
+        let simulation = simulate(&index, &parse.numbers, runs, opts.seed);
+        println!("board,first,last,mean draw,p10,median,p90,never");
+        for (i, stats) in simulation.boards.iter().enumerate() {
+            let draw = |d: Option<usize>| d.map_or_else(String::new, |d| d.to_string());
+            println!(
+                "{},{:.4},{:.4},{},{},{},{},{}",
+                i,
+                simulation.p_first(i),
+                simulation.p_last(i),
+                stats
+                    .mean()
+                    .map_or_else(String::new, |mean| format!("{:.2}", mean)),
+                draw(stats.quantile(0.1)),
+                draw(stats.quantile(0.5)),
+                draw(stats.quantile(0.9)),
+                stats.never,
+            );
+        }
+        return Ok(());
+    }
 
-    for (i, (board, score)) in numbers
-        .into_iter()
-        .filter_map(|n| index.call_number(n))
-        .flat_map(|w| w.into_iter())
-        .enumerate()
-    {
-        println!("{}. board {} won with score of {}", i, board, score);
+    for (i, win) in play(&parse.numbers, index).enumerate() {
+        let by: Vec<String> = win
+            .patterns
+            .iter()
+            .map(|&p| patterns[p].to_string())
+            .collect();
+        println!(
+            "{}. board {} won by {} with score of {}",
+            i,
+            win.board,
+            by.join(" and "),
+            win.score
+        );
     }
 
     Ok(())
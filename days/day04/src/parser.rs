@@ -1,60 +1,264 @@
+use std::{fmt::Display, str::FromStr};
+
+use common::Diagnostic;
 use nom::{
     bytes::complete::tag,
-    character::complete::{digit1, newline, space0, space1},
-    combinator::map_res,
-    multi::{many1, separated_list1},
+    character::complete::{digit1, multispace0, newline, space0, space1},
+    combinator::{cut, eof, map_res, not, peek, verify},
+    error::{context, VerboseError},
+    multi::{count, many0, many1, separated_list1},
     sequence::{preceded, terminated, tuple},
-    IResult,
+    Finish,
 };
+use thiserror::Error;
+
+type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
 
-pub type LineOfNumbers = Vec<u8>;
+/// A number which can be drawn or be on a board.
+pub type Number = u32;
+
+pub type LineOfNumbers = Vec<Number>;
 pub type Board = Vec<LineOfNumbers>;
 pub type Boards = Vec<Board>;
 
-fn parse_number(input: &str) -> Result<u8, std::num::ParseIntError> {
+/// How many rows and columns every board has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape {
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Shape {
+    /// The shape of a board, or anything else laid out like one, going by its first row.
+    pub fn of<T>(board: &[Vec<T>]) -> Self {
+        Self {
+            rows: board.len(),
+            cols: board.first().map_or(0, Vec::len),
+        }
+    }
+
+    /// How many numbers a board of this shape has.
+    pub fn cells(self) -> usize {
+        self.rows * self.cols
+    }
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.rows, self.cols)
+    }
+}
+
+impl FromStr for Shape {
+    type Err = InvalidShape;
+
+    /// Reads a shape written as `rows`x`cols`, like `5x5`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidShape(s.to_string());
+        let (rows, cols) = s.split_once('x').ok_or_else(invalid)?;
+        let rows = rows.parse().map_err(|_| invalid())?;
+        let cols = cols.parse().map_err(|_| invalid())?;
+
+        if rows == 0 || cols == 0 {
+            return Err(invalid());
+        }
+        Ok(Self { rows, cols })
+    }
+}
+
+#[derive(Debug, Error)]
+#[error("{0} is not a board shape. Expected rows and columns like 5x5")]
+pub struct InvalidShape(String);
+
+fn parse_number(input: &str) -> Result<Number, std::num::ParseIntError> {
     input.parse()
 }
 
-fn number(input: &str) -> IResult<&str, u8> {
-    map_res(digit1, parse_number)(input)
+fn number(input: &str) -> IResult<'_, Number> {
+    context(
+        "a number from 0 to 4294967295",
+        map_res(digit1, parse_number),
+    )(input)
 }
 
-fn number_line(input: &str) -> IResult<&str, LineOfNumbers> {
-    separated_list1(tag(","), number)(input)
+fn number_line(input: &str) -> IResult<'_, LineOfNumbers> {
+    separated_list1(tag(","), cut(number))(input)
 }
 
-fn board_line(input: &str) -> IResult<&str, LineOfNumbers> {
+fn board_line(input: &str) -> IResult<'_, LineOfNumbers> {
     separated_list1(space1, number)(input)
 }
 
-fn board(input: &str) -> IResult<&str, Board> {
-    separated_list1(terminated(newline, space0), board_line)(input)
+/// The end of one row of a board and the start of the next, which is told apart from the end of
+/// the board by there being a number straight after it rather than a blank line.
+fn next_row(input: &str) -> IResult<'_, char> {
+    terminated(terminated(newline, space0), peek(digit1))(input)
+}
+
+/// A row of a board with `cols` numbers in it.
+fn row<'a>(cols: usize) -> impl FnMut(&'a str) -> IResult<'a, LineOfNumbers> {
+    context(
+        "a row as wide as the rest of the boards",
+        cut(verify(board_line, move |line: &LineOfNumbers| {
+            line.len() == cols
+        })),
+    )
+}
+
+/// The first board, which decides how big the rest of the boards are. It can have any number of
+/// rows, as long as they're all as wide as each other.
+fn first_board(input: &str) -> IResult<'_, Board> {
+    let (input, first) = board_line(input)?;
+    let (input, rest) = many0(preceded(next_row, row(first.len())))(input)?;
+
+    let mut board = vec![first];
+    board.extend(rest);
+    Ok((input, board))
 }
 
-fn boards(input: &str) -> IResult<&str, Boards> {
-    separated_list1(many1(terminated(newline, space0)), board)(input)
+/// A board of the given shape. Once a board has started, it's an error for it to be any other
+/// shape, rather than the start of something else.
+fn board<'a>(shape: Shape) -> impl FnMut(&'a str) -> IResult<'a, Board> {
+    move |input| {
+        let (input, _) = peek(digit1)(input)?;
+        let (input, first) = row(shape.cols)(input)?;
+        let (input, rest) = count(
+            preceded(
+                context("another row of the board", cut(next_row)),
+                row(shape.cols),
+            ),
+            shape.rows.saturating_sub(1),
+        )(input)?;
+        let (input, _) = context(
+            "a blank line after the last row of the board",
+            cut(not(next_row)),
+        )(input)?;
+
+        let mut board = vec![first];
+        board.extend(rest);
+        Ok((input, board))
+    }
+}
+
+/// The boards, which are all the same shape as the first unless a shape is given.
+fn boards(input: &str, shape: Option<Shape>) -> IResult<'_, Boards> {
+    let (input, first) = match shape {
+        Some(shape) => board(shape)(input)?,
+        None => first_board(input)?,
+    };
+    let shape = Shape::of(&first);
+    let (input, rest) = many0(preceded(many1(terminated(newline, space0)), board(shape)))(input)?;
+
+    let mut boards = vec![first];
+    boards.extend(rest);
+    Ok((input, boards))
 }
 
-fn total_input(input: &str) -> IResult<&str, (LineOfNumbers, Boards)> {
-    tuple((terminated(number_line, newline), preceded(newline, boards)))(input)
+fn total_input(input: &str, shape: Option<Shape>) -> IResult<'_, (LineOfNumbers, Boards)> {
+    terminated(
+        tuple((
+            terminated(
+                number_line,
+                context("a `,` between the numbers to draw", newline),
+            ),
+            preceded(
+                context(
+                    "a blank line before the boards",
+                    terminated(newline, space0),
+                ),
+                |input| boards(input, shape),
+            ),
+        )),
+        context(
+            "a board number or the end of the input",
+            preceded(multispace0, eof),
+        ),
+    )(input)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Parse {
     pub numbers: LineOfNumbers,
     pub boards: Boards,
 }
 
-pub fn parse(input: &str) -> color_eyre::Result<Parse> {
-    let (_, (numbers, boards)) = total_input(input).map_err(|e| e.to_owned())?;
+impl Parse {
+    /// The shape of the boards, or `None` if there aren't any.
+    pub fn shape(&self) -> Option<Shape> {
+        self.boards.first().map(|board| Shape::of(board))
+    }
+}
+
+/// Parses the numbers to draw and the boards, working out how big the boards are from the first.
+pub fn parse(input: &str) -> Result<Parse, Diagnostic> {
+    parse_with(input, None)
+}
 
-    Ok(Parse { numbers, boards })
+/// Parses the numbers to draw and the boards, which must all be the given shape.
+pub fn parse_shaped(input: &str, shape: Shape) -> Result<Parse, Diagnostic> {
+    parse_with(input, Some(shape))
+}
+
+fn parse_with(input: &str, shape: Option<Shape>) -> Result<Parse, Diagnostic> {
+    total_input(input, shape)
+        .finish()
+        .map(|(_, (numbers, boards))| Parse { numbers, boards })
+        .map_err(|e| Diagnostic::from_nom(input, e))
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::{collection::vec, prelude::*};
+
     use super::*;
 
+    /// Writes the numbers and boards out the way the puzzle input does, right aligning numbers
+    /// so that single digits have a leading space.
+    fn render(parse: &Parse) -> String {
+        let numbers: Vec<_> = parse.numbers.iter().map(|n| n.to_string()).collect();
+        let boards: Vec<_> = parse
+            .boards
+            .iter()
+            .map(|board| {
+                board
+                    .iter()
+                    .map(|line| {
+                        line.iter()
+                            .map(|n| format!("{:>2}", n))
+                            .collect::<Vec<_>>()
+                            .join(" ")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect();
+
+        format!("{}\n\n{}\n", numbers.join(","), boards.join("\n\n"))
+    }
+
+    fn parses() -> impl Strategy<Value = Parse> {
+        (1..8usize, 1..8usize)
+            .prop_flat_map(|(rows, cols)| {
+                (
+                    vec(any::<Number>(), 1..100),
+                    vec(vec(vec(any::<Number>(), cols), rows), 1..10),
+                )
+            })
+            .prop_map(|(numbers, boards)| Parse { numbers, boards })
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(parse in parses()) {
+            prop_assert_eq!(parse.clone(), super::parse(&render(&parse)).unwrap());
+        }
+
+        #[test]
+        fn test_does_not_panic(input in "\\PC*") {
+            let _ = super::parse(&input);
+        }
+    }
+
     #[test]
     fn test_parse() {
         let input = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
@@ -77,6 +281,53 @@ mod tests {
 22 11 13  6  5
 2  0 12  3  7";
 
-        parse(input).expect("should be able to successfully parse the test input");
+        let parse = parse(input).expect("should be able to successfully parse the test input");
+        assert_eq!(Some(Shape { rows: 5, cols: 5 }), parse.shape());
+    }
+
+    #[test]
+    fn test_shape_from_first_board() {
+        let input = "1,2,3\n\n1 2 3\n4 5 6\n\n7 8 9\n10 11 12\n";
+
+        let parse = parse(input).unwrap();
+        assert_eq!(Some(Shape { rows: 2, cols: 3 }), parse.shape());
+        assert_eq!(2, parse.boards.len());
+    }
+
+    #[test]
+    fn test_inconsistent_boards() {
+        let ragged = "1,2\n\n1 2 3\n4 5\n";
+        let error = parse(ragged).unwrap_err();
+        assert_eq!(
+            (4, "a row as wide as the rest of the boards"),
+            (error.line, &*error.expected)
+        );
+
+        let short = "1,2\n\n1 2\n3 4\n\n5 6\n\n7 8\n9 10\n";
+        let error = parse(short).unwrap_err();
+        assert_eq!(
+            (7, "another row of the board"),
+            (error.line, &*error.expected)
+        );
+
+        let long = "1,2\n\n1 2\n3 4\n\n5 6\n7 8\n9 10\n";
+        let error = parse(long).unwrap_err();
+        assert_eq!(
+            (7, "a blank line after the last row of the board"),
+            (error.line, &*error.expected)
+        );
+    }
+
+    #[test]
+    fn test_given_shape() {
+        let input = "1,2\n\n1 2\n3 4\n";
+        let shape = |s: &str| s.parse::<Shape>().unwrap();
+
+        assert!(parse_shaped(input, shape("2x2")).is_ok());
+        assert_eq!(3, parse_shaped(input, shape("2x3")).unwrap_err().line);
+        assert_eq!(5, parse_shaped(input, shape("3x2")).unwrap_err().line);
+
+        assert!("0x5".parse::<Shape>().is_err());
+        assert!("5".parse::<Shape>().is_err());
     }
 }
@@ -0,0 +1,221 @@
+//! The ways a board can win. Each [Pattern] picks out one or more sets of cells on a board, and a
+//! board wins as soon as every number in one of those sets has been called.
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use crate::parser::Shape;
+
+/// A cell on a board, as its row and column.
+pub type Cell = (usize, usize);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    /// Any complete row
+    Row,
+
+    /// Any complete column
+    Column,
+
+    /// Either diagonal, which only square boards have
+    Diagonal,
+
+    /// All four corners
+    Corners,
+
+    /// Every number on the board
+    Blackout,
+
+    /// The cells set in a mask the same shape as the board, which is written a row at a time with
+    /// `#` for the cells to cover and `.` for the rest, separating rows with `/`. An X on a 3x3
+    /// board is `#.#/.#./#.#`.
+    Mask(Vec<Vec<bool>>),
+}
+
+impl Pattern {
+    /// The patterns of the original game.
+    pub const DEFAULT: [Pattern; 2] = [Pattern::Row, Pattern::Column];
+
+    /// Every set of cells on a board of the given shape which wins it.
+    pub fn lines(&self, shape: Shape) -> Result<Vec<Vec<Cell>>, PatternError> {
+        let Shape { rows, cols } = shape;
+
+        let lines = match self {
+            Self::Row => (0..rows)
+                .map(|row| (0..cols).map(|col| (row, col)).collect())
+                .collect(),
+            Self::Column => (0..cols)
+                .map(|col| (0..rows).map(|row| (row, col)).collect())
+                .collect(),
+            Self::Diagonal => {
+                if rows != cols {
+                    return Err(PatternError::NotSquare(shape));
+                }
+                vec![
+                    (0..rows).map(|i| (i, i)).collect(),
+                    (0..rows).map(|i| (i, cols - 1 - i)).collect(),
+                ]
+            }
+            Self::Corners => {
+                let mut corners = vec![(0, 0), (0, cols - 1), (rows - 1, 0), (rows - 1, cols - 1)];
+                corners.sort_unstable();
+                corners.dedup();
+                vec![corners]
+            }
+            Self::Blackout => vec![(0..rows)
+                .flat_map(|row| (0..cols).map(move |col| (row, col)))
+                .collect()],
+            Self::Mask(mask) => {
+                let mask_shape = Shape::of(mask);
+                if mask_shape != shape {
+                    return Err(PatternError::WrongShape {
+                        mask: mask_shape,
+                        board: shape,
+                    });
+                }
+                vec![mask
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(row, line)| {
+                        line.iter()
+                            .enumerate()
+                            .filter_map(move |(col, &set)| set.then_some((row, col)))
+                    })
+                    .collect()]
+            }
+        };
+
+        Ok(lines)
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Row => f.write_str("row"),
+            Self::Column => f.write_str("column"),
+            Self::Diagonal => f.write_str("diagonal"),
+            Self::Corners => f.write_str("corners"),
+            Self::Blackout => f.write_str("blackout"),
+            Self::Mask(mask) => {
+                let rows: Vec<String> = mask
+                    .iter()
+                    .map(|row| row.iter().map(|&set| if set { '#' } else { '.' }).collect())
+                    .collect();
+                f.write_str(&rows.join("/"))
+            }
+        }
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = PatternError;
+
+    /// Reads a pattern by name, or a mask made of `#`, `.` and `/`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "row" => return Ok(Self::Row),
+            "column" => return Ok(Self::Column),
+            "diagonal" => return Ok(Self::Diagonal),
+            "corners" => return Ok(Self::Corners),
+            "blackout" => return Ok(Self::Blackout),
+            _ => (),
+        }
+
+        if !s.chars().all(|c| matches!(c, '#' | '.' | '/')) {
+            return Err(PatternError::Unknown(s.to_string()));
+        }
+
+        let mask: Vec<Vec<bool>> = s
+            .split('/')
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect();
+
+        let cols = mask[0].len();
+        if cols == 0 || mask.iter().any(|row| row.len() != cols) {
+            return Err(PatternError::Ragged(s.to_string()));
+        }
+        if !mask.iter().flatten().any(|&set| set) {
+            return Err(PatternError::Empty(s.to_string()));
+        }
+
+        Ok(Self::Mask(mask))
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PatternError {
+    #[error("{0} is not a pattern. Expected row, column, diagonal, corners, blackout or a mask like #.#/.#./#.#")]
+    Unknown(String),
+
+    #[error("the rows of the mask {0} aren't all the same width")]
+    Ragged(String),
+
+    #[error("the mask {0} doesn't cover any cells")]
+    Empty(String),
+
+    #[error("only square boards have diagonals, but the boards are {0}")]
+    NotSquare(Shape),
+
+    #[error("the mask is {mask}, but the boards are {board}")]
+    WrongShape { mask: Shape, board: Shape },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pattern, PatternError};
+    use crate::parser::Shape;
+
+    #[test]
+    fn test_lines() {
+        let square = Shape { rows: 3, cols: 3 };
+
+        assert_eq!(3, Pattern::Row.lines(square).unwrap().len());
+        assert_eq!(
+            vec![vec![(0, 0), (1, 1), (2, 2)], vec![(0, 2), (1, 1), (2, 0)]],
+            Pattern::Diagonal.lines(square).unwrap()
+        );
+        assert_eq!(
+            vec![vec![(0, 0), (0, 2), (2, 0), (2, 2)]],
+            Pattern::Corners.lines(square).unwrap()
+        );
+        assert_eq!(9, Pattern::Blackout.lines(square).unwrap()[0].len());
+
+        let plus: Pattern = ".#./###/.#.".parse().unwrap();
+        assert_eq!(
+            vec![vec![(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)]],
+            plus.lines(square).unwrap()
+        );
+        assert_eq!(".#./###/.#.", plus.to_string());
+    }
+
+    #[test]
+    fn test_errors() {
+        let wide = Shape { rows: 2, cols: 3 };
+
+        assert_eq!(
+            Err(PatternError::NotSquare(wide)),
+            Pattern::Diagonal.lines(wide)
+        );
+        assert_eq!(
+            Err(PatternError::WrongShape {
+                mask: Shape { rows: 2, cols: 2 },
+                board: wide
+            }),
+            "#./.#".parse::<Pattern>().unwrap().lines(wide)
+        );
+
+        assert!(matches!(
+            "star".parse::<Pattern>(),
+            Err(PatternError::Unknown(_))
+        ));
+        assert!(matches!(
+            "##/#".parse::<Pattern>(),
+            Err(PatternError::Ragged(_))
+        ));
+        assert!(matches!(
+            "../..".parse::<Pattern>(),
+            Err(PatternError::Empty(_))
+        ));
+    }
+}
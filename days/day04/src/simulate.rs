@@ -0,0 +1,228 @@
+//! Playing the same boards over and over with the numbers drawn in a random order, to see how
+//! likely each board is to win first or last, and how long it usually takes to win.
+//!
+//! Each run shuffles the draws with its own stream of a seeded RNG, so the results only depend on
+//! the seed and the number of runs, not on how the runs were shared out between threads.
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+
+use crate::{index::Index, parser::Number};
+
+/// How one board did over every run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoardStats {
+    /// How many runs the board was the first to win. Boards which win on the same draw are all
+    /// counted as first.
+    pub first: usize,
+
+    /// How many runs the board was the last to win, counting ties the same way as [first](Self::first)
+    pub last: usize,
+
+    /// How many runs the board won on each draw, where `wins[d]` counts wins on draw `d + 1`
+    pub wins: Vec<usize>,
+
+    /// How many runs the board didn't win at all, which happens when the draws don't cover it
+    pub never: usize,
+}
+
+impl BoardStats {
+    fn new(draws: usize) -> Self {
+        Self {
+            wins: vec![0; draws],
+            ..Self::default()
+        }
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        self.first += other.first;
+        self.last += other.last;
+        self.never += other.never;
+        for (wins, other) in self.wins.iter_mut().zip(other.wins) {
+            *wins += other;
+        }
+        self
+    }
+
+    /// The average draw the board won on, or `None` if it never won.
+    pub fn mean(&self) -> Option<f64> {
+        let runs: usize = self.wins.iter().sum();
+        let total: usize = self.wins.iter().enumerate().map(|(d, n)| (d + 1) * n).sum();
+        (runs > 0).then(|| total as f64 / runs as f64)
+    }
+
+    /// The draw by which the board had won in at least the fraction `q` of the runs it won, or
+    /// `None` if it never won.
+    pub fn quantile(&self, q: f64) -> Option<usize> {
+        let runs: usize = self.wins.iter().sum();
+        let target = (q * runs as f64).ceil().max(1.0) as usize;
+
+        let mut seen = 0;
+        self.wins
+            .iter()
+            .position(|&n| {
+                seen += n;
+                seen >= target
+            })
+            .map(|d| d + 1)
+    }
+}
+
+/// The results of a simulation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simulation {
+    pub runs: usize,
+    pub boards: Vec<BoardStats>,
+}
+
+impl Simulation {
+    fn new(boards: usize, draws: usize) -> Self {
+        Self {
+            runs: 0,
+            boards: vec![BoardStats::new(draws); boards],
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            runs: self.runs + other.runs,
+            boards: self
+                .boards
+                .into_iter()
+                .zip(other.boards)
+                .map(|(l, r)| l.merge(r))
+                .collect(),
+        }
+    }
+
+    /// How likely the board is to win first.
+    pub fn p_first(&self, board: usize) -> f64 {
+        self.boards[board].first as f64 / self.runs as f64
+    }
+
+    /// How likely the board is to win last.
+    pub fn p_last(&self, board: usize) -> f64 {
+        self.boards[board].last as f64 / self.runs as f64
+    }
+}
+
+/// The state one batch of runs needs, which is set up once and reused for every run in the batch.
+struct Runner {
+    index: Index,
+    draws: Vec<Number>,
+
+    /// The draw each board won on in the current run
+    won: Vec<Option<usize>>,
+    simulation: Simulation,
+}
+
+impl Runner {
+    fn new(index: &Index, draws: &[Number]) -> Self {
+        Self {
+            index: index.clone(),
+            draws: draws.to_vec(),
+            won: vec![None; index.boards()],
+            simulation: Simulation::new(index.boards(), draws.len()),
+        }
+    }
+
+    /// Plays one game, shuffling `draws` with the stream of the RNG for this run. The draws are
+    /// copied over first rather than shuffling the last run's order again, so each run only
+    /// depends on its own stream.
+    fn run(mut self, draws: &[Number], seed: u64, run: usize) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_stream(run as u64);
+        self.draws.copy_from_slice(draws);
+        self.draws.shuffle(&mut rng);
+
+        self.index.reset();
+        self.won.fill(None);
+
+        let mut left = self.won.len();
+        for (d, &n) in self.draws.iter().enumerate() {
+            if left == 0 {
+                break;
+            }
+            for win in self.index.call_number(n).into_iter().flatten() {
+                if self.won[win.board].is_none() {
+                    self.won[win.board] = Some(d);
+                    left -= 1;
+                }
+            }
+        }
+
+        let first = self.won.iter().flatten().min().copied();
+        let last = self.won.iter().flatten().max().copied();
+        for (stats, won) in self.simulation.boards.iter_mut().zip(&self.won) {
+            match won {
+                Some(d) => {
+                    stats.wins[*d] += 1;
+                    stats.first += usize::from(Some(*d) == first);
+                    stats.last += usize::from(Some(*d) == last);
+                }
+                None => stats.never += 1,
+            }
+        }
+
+        self.simulation.runs += 1;
+        self
+    }
+}
+
+/// Plays the boards in the index `runs` times, each time drawing the numbers in a different order
+/// picked using `seed`. The runs are shared out between threads by splitting them into batches, and
+/// each batch clones the index once and resets it between runs. There can be more batches than
+/// threads, since rayon splits the work further to balance it out.
+pub fn simulate(index: &Index, draws: &[Number], runs: usize, seed: u64) -> Simulation {
+    (0..runs)
+        .into_par_iter()
+        .fold(
+            || Runner::new(index, draws),
+            |runner, run| runner.run(draws, seed, run),
+        )
+        .map(|runner| runner.simulation)
+        .reduce(
+            || Simulation::new(index.boards(), draws.len()),
+            Simulation::merge,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::ThreadPoolBuilder;
+
+    use super::simulate;
+    use crate::{index::Index, parser};
+
+    const EXAMPLE: &str = include_str!("../input/test.txt");
+
+    #[test]
+    fn test_simulate() {
+        let parse = parser::parse(EXAMPLE).unwrap();
+        let index = Index::from(parse.boards.clone());
+        let simulation = simulate(&index, &parse.numbers, 500, 7);
+
+        assert_eq!(500, simulation.runs);
+        for stats in &simulation.boards {
+            // the draws cover every board, and a board needs at least 5 numbers to win
+            assert_eq!(0, stats.never);
+            assert_eq!(500, stats.wins.iter().sum::<usize>());
+            assert!(stats.wins[..4].iter().all(|&n| n == 0));
+        }
+
+        let first: usize = simulation.boards.iter().map(|s| s.first).sum();
+        let last: usize = simulation.boards.iter().map(|s| s.last).sum();
+        assert!(first >= 500 && last >= 500);
+
+        // the same seed gives the same results, however many threads share the runs
+        for threads in [1, 4] {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let again = pool.install(|| simulate(&index, &parse.numbers, 500, 7));
+            assert_eq!(simulation, again);
+        }
+        assert_ne!(simulation, simulate(&index, &parse.numbers, 500, 8));
+    }
+}
@@ -0,0 +1,29 @@
+use rand::Rng;
+
+/// How many lines of vents the official input has.
+pub const DEFAULT_SIZE: usize = 500;
+
+/// Vents lie on a grid this many points across.
+const GRID: i32 = 1000;
+
+/// Generates `size` lines of vents, evenly split between horizontal, vertical and diagonal lines.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let (x1, y1) = (rng.gen_range(0..GRID), rng.gen_range(0..GRID));
+            let (x2, y2) = match rng.gen_range(0..3) {
+                0 => (rng.gen_range(0..GRID), y1),
+                1 => (x1, rng.gen_range(0..GRID)),
+                _ => {
+                    // head towards the middle so there's always room for a diagonal
+                    let dx = if x1 < GRID / 2 { 1 } else { -1 };
+                    let dy = if y1 < GRID / 2 { 1 } else { -1 };
+                    let len = rng.gen_range(1..GRID / 2);
+                    (x1 + dx * len, y1 + dy * len)
+                }
+            };
+
+            format!("{},{} -> {},{}\n", x1, y1, x2, y2)
+        })
+        .collect()
+}
@@ -0,0 +1,44 @@
+use common::{Day, Diagnostic};
+use model::{Arrow, Board};
+
+pub mod generate;
+pub mod model;
+pub mod parser;
+
+/// Counts the points where at least two of the given vents overlap.
+pub fn count_overlaps<'a>(arrows: impl Iterator<Item = &'a Arrow>) -> usize {
+    let mut board = Board::default();
+
+    for arrow in arrows {
+        board.push(arrow);
+    }
+
+    board.solutions().count()
+}
+
+pub struct Day05;
+
+impl Day for Day05 {
+    const DAY: usize = 5;
+
+    type Input = Vec<Arrow>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| line.parse().map_err(|e: Diagnostic| e.below(i)))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        // part 1 only considers horizontal and vertical vents
+        Ok(count_overlaps(input.iter().filter(|a| a.is_straight())))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        Ok(count_overlaps(input.iter()))
+    }
+}
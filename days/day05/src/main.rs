@@ -1,38 +1,31 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::path::PathBuf;
 
-use model::Board;
+use common::{input, Day};
+use day05::Day05;
 use structopt::StructOpt;
 
-mod model;
-mod parser;
-
 #[derive(StructOpt)]
 #[structopt(
     name = "aoc2021-day-5",
     about = "The fifth day of advent of code. Note: this command will only solve part 2"
 )]
 struct Cli {
-    #[structopt(parse(from_os_str), help = "the input file from adventofcode")]
-    input: PathBuf,
+    #[structopt(
+        parse(from_os_str),
+        help = "the input file from adventofcode. Reads from stdin if omitted or `-`"
+    )]
+    input: Option<PathBuf>,
 }
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let opts = Cli::from_args();
 
-    let reader = BufReader::new(File::open(opts.input)?);
-
-    let mut board = Board::default();
+    let buf = input::read_to_string(opts.input.as_deref())?;
 
-    for line in reader.lines() {
-        board.push(&line?.parse()?);
-    }
+    let arrows = Day05::parse(&buf)?;
 
-    println!("solution: {}", board.solutions().count());
+    println!("solution: {}", Day05::part_2(&arrows)?);
 
     Ok(())
 }
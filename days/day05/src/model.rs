@@ -1,5 +1,6 @@
 use std::{iter::repeat, str::FromStr};
 
+use common::Diagnostic;
 use fxhash::FxHashMap;
 
 use crate::parser;
@@ -44,14 +45,14 @@ impl std::ops::Sub for &Point {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Arrow {
     pub from: Point,
     pub to: Point,
 }
 
 impl FromStr for Arrow {
-    type Err = nom::error::Error<String>;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         parser::parse(s)
@@ -65,6 +66,10 @@ impl From<(Point, Point)> for Arrow {
 }
 
 impl Arrow {
+    pub fn is_straight(&self) -> bool {
+        self.from.x == self.to.x || self.from.y == self.to.y
+    }
+
     pub fn iter(&self) -> Box<dyn Iterator<Item = Point>> {
         match &self.to - &self.from {
             Point { x: 0, y } => Box::new(
@@ -1,35 +1,88 @@
 use std::str::FromStr;
 
+use common::Diagnostic;
 use nom::{
     bytes::complete::tag,
     character::complete::char,
     character::complete::{digit1, space0},
-    combinator::{map, map_res},
-    sequence::{delimited, separated_pair},
-    Finish, IResult,
+    combinator::{eof, map, map_res},
+    error::{context, VerboseError},
+    sequence::{delimited, preceded, separated_pair, terminated},
+    Finish,
 };
 
 use crate::model::{Arrow, Point};
 
-fn number(input: &str) -> IResult<&str, i32> {
-    map_res(digit1, FromStr::from_str)(input)
+type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+fn number(input: &str) -> IResult<'_, i32> {
+    context("a number", map_res(digit1, FromStr::from_str))(input)
 }
 
-fn point(input: &str) -> IResult<&str, Point> {
-    map(separated_pair(number, char(','), number), From::from)(input)
+fn point(input: &str) -> IResult<'_, Point> {
+    map(
+        separated_pair(
+            number,
+            context("`,` between coordinates", char(',')),
+            number,
+        ),
+        From::from,
+    )(input)
 }
 
-fn arrow(input: &str) -> IResult<&str, &str> {
-    delimited(space0, tag("->"), space0)(input)
+fn arrow(input: &str) -> IResult<'_, &str> {
+    context("`->` between points", delimited(space0, tag("->"), space0))(input)
 }
 
-fn statement(input: &str) -> IResult<&str, Arrow> {
-    map(separated_pair(point, arrow, point), From::from)(input)
+fn statement(input: &str) -> IResult<'_, Arrow> {
+    terminated(
+        map(separated_pair(point, arrow, point), From::from),
+        context("the end of the line", eof),
+    )(input)
 }
 
-pub fn parse(input: &str) -> Result<Arrow, nom::error::Error<String>> {
-    statement(input.trim())
+/// Parses a single line of vents.
+pub fn parse(input: &str) -> Result<Arrow, Diagnostic> {
+    let input = input.trim_end();
+
+    preceded(space0, statement)(input)
         .finish()
         .map(|(_, a)| a)
-        .map_err(|e| nom::error::Error::new(e.input.to_string(), e.code))
+        .map_err(|e| Diagnostic::from_nom(input, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::parse;
+    use crate::model::Arrow;
+
+    fn arrows() -> impl Strategy<Value = Arrow> {
+        let coordinate = || 0..=i32::MAX;
+
+        (coordinate(), coordinate(), coordinate(), coordinate())
+            .prop_map(|(x1, y1, x2, y2)| Arrow::from(((x1, y1).into(), (x2, y2).into())))
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(arrow in arrows()) {
+            let text = format!("{},{} -> {},{}", arrow.from.x, arrow.from.y, arrow.to.x, arrow.to.y);
+            prop_assert_eq!(arrow, parse(&text).unwrap());
+        }
+
+        #[test]
+        fn test_does_not_panic(input in "\\PC*") {
+            let _ = parse(&input);
+        }
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let diagnostic = parse("0,9 => 5,9").unwrap_err();
+
+        assert_eq!((diagnostic.line, diagnostic.column), (1, 5));
+        assert_eq!(diagnostic.expected, "`->` between points");
+    }
 }
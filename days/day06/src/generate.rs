@@ -0,0 +1,13 @@
+use rand::Rng;
+
+/// How many fish the official input has.
+pub const DEFAULT_SIZE: usize = 300;
+
+/// Generates `size` fish, each with an internal timer between 1 and 5.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let fish: Vec<_> = (0..size)
+        .map(|_| rng.gen_range(1..=5).to_string())
+        .collect();
+
+    format!("{}\n", fish.join(","))
+}
@@ -0,0 +1,92 @@
+use common::{Day, Diagnostic};
+use rayon::prelude::*;
+
+pub mod generate;
+
+/// The number of days the puzzle asks us to simulate for part 1.
+pub const PART_1_DAYS: usize = 80;
+
+/// The number of days the puzzle asks us to simulate for part 2.
+pub const PART_2_DAYS: usize = 256;
+
+pub fn parse(input: &str) -> Result<Vec<i8>, Diagnostic> {
+    let mut offset = 0;
+    input
+        .split(',')
+        .map(|n| {
+            let start = offset + n.len() - n.trim_start().len();
+            offset += n.len() + 1;
+            n.trim()
+                .parse()
+                .map_err(|_| Diagnostic::at_offset(input, start, "a fish timer between commas"))
+        })
+        .collect()
+}
+
+/// Simulates every fish individually. Much slower than [simulate].
+pub fn simulate_brute(mut fish: Vec<i8>, days: usize) -> usize {
+    let mut new_fish = 0;
+    for _ in 0..days {
+        let next_new_fish = fish
+            .par_iter_mut()
+            .fold(
+                || 0,
+                |c, i| {
+                    *i -= 1;
+                    match *i {
+                        0 => c + 1,
+                        x if x < 0 => {
+                            *i = 6;
+                            c
+                        }
+                        _ => c,
+                    }
+                },
+            )
+            .sum();
+        fish.resize(fish.len() + new_fish, 8);
+        new_fish = next_new_fish;
+    }
+
+    fish.len()
+}
+
+/// Simulates the fish by counting how many of them share each timer value.
+pub fn simulate(fish: &[i8], days: usize) -> usize {
+    let mut fish_count = [0usize; 9];
+
+    for &f in fish {
+        fish_count[f as usize] += 1;
+    }
+
+    for _ in 0..days {
+        let next_fish = fish_count[0];
+        fish_count.rotate_left(1);
+        fish_count[6] += next_fish;
+        fish_count[8] = next_fish;
+    }
+
+    fish_count.iter().sum()
+}
+
+pub struct Day06;
+
+impl Day for Day06 {
+    const DAY: usize = 6;
+
+    type Input = Vec<i8>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(crate::parse(input)?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        Ok(simulate(input, PART_1_DAYS))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        Ok(simulate(input, PART_2_DAYS))
+    }
+}
@@ -0,0 +1,14 @@
+use rand::Rng;
+
+/// How many crabs the official input has.
+pub const DEFAULT_SIZE: usize = 1000;
+
+/// Generates the horizontal positions of `size` crabs between 0 and 2000. Keep `size` to a few
+/// thousand, as the part 2 fuel cost is tracked in an `i32`.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let crabs: Vec<_> = (0..size)
+        .map(|_| rng.gen_range(0..2000).to_string())
+        .collect();
+
+    format!("{}\n", crabs.join(","))
+}
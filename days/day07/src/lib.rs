@@ -0,0 +1,89 @@
+use common::{Day, Diagnostic};
+
+pub mod generate;
+
+/// Parses the crab positions and returns them sorted.
+pub fn parse(input: &str) -> Result<Vec<i32>, Diagnostic> {
+    let mut offset = 0;
+    let mut data = input
+        .split(',')
+        .map(|s| {
+            let start = offset + s.len() - s.trim_start().len();
+            offset += s.len() + 1;
+            s.trim()
+                .parse()
+                .map_err(|_| Diagnostic::at_offset(input, start, "a crab position between commas"))
+        })
+        .collect::<Result<Vec<i32>, Diagnostic>>()?;
+
+    data.sort_unstable();
+
+    Ok(data)
+}
+
+/// Expects `data` to be sorted.
+pub fn part_1(data: &[i32]) -> i32 {
+    let (&start, &end) = data.first().zip(data.last()).expect("there was no input");
+
+    let mut score = (data.len() as i32) + data.iter().map(|n| n - start).sum::<i32>();
+    let mut min = score;
+    let mut index = 0;
+
+    for i in start..=end {
+        while data[index] < i {
+            index += 1;
+        }
+
+        let left = &data[..index];
+        let right = &data[index..];
+
+        score += left.len() as i32;
+        score -= right.len() as i32;
+        min = min.min(score);
+    }
+
+    min
+}
+
+/// Expects `data` to be sorted.
+pub fn part_2(data: &[i32]) -> i32 {
+    let (&start, &end) = data.first().zip(data.last()).expect("there was no input");
+
+    let mut min = i32::MAX;
+
+    //TODO(Dan): is there a faster way to do this similar to the way we do it for part 1?
+    for i in start..=end {
+        let cost = data
+            .iter()
+            .map(|n| (n - i).abs())
+            .map(|n| n + 1)
+            .map(|n| (n * (n - 1)) / 2)
+            .sum();
+
+        min = min.min(cost);
+    }
+
+    min
+}
+
+pub struct Day07;
+
+impl Day for Day07 {
+    const DAY: usize = 7;
+
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(crate::parse(input)?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        Ok(crate::part_1(input))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        Ok(crate::part_2(input))
+    }
+}
@@ -0,0 +1,36 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day08::{
+    model::{solve, Solver},
+    parser::Input,
+};
+
+const EXAMPLE: &str = include_str!("../input/test.txt");
+
+/// Solves every line of the input with a single reused solver, like `part_2` does.
+fn solve_lines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day08/solve");
+
+    for copies in [1, 1000] {
+        let input: Input = EXAMPLE.repeat(copies).parse().expect("example must parse");
+        let lines = input.lines().count();
+
+        group.throughput(Throughput::Elements(lines as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(lines), &input, |b, input| {
+            let mut solver = Solver::default();
+
+            b.iter(|| {
+                for line in input.lines() {
+                    solver.reset();
+                    black_box(solve(line, &mut solver).expect("example lines are solvable"));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, solve_lines);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+use rand::{seq::SliceRandom, Rng};
+
+/// How many displays the official input has.
+pub const DEFAULT_SIZE: usize = 200;
+
+/// The segments lit for each digit on a correctly wired display.
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// Generates `size` displays, each with its own scrambled wiring. Every line lists all ten
+/// unique patterns in a random order followed by four output digits.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut wiring: Vec<char> = "abcdefg".chars().collect();
+            wiring.shuffle(rng);
+
+            let mut patterns: Vec<_> = DIGITS
+                .iter()
+                .map(|digit| scramble(rng, digit, &wiring))
+                .collect();
+            patterns.shuffle(rng);
+
+            let output: Vec<_> = (0..4)
+                .map(|_| {
+                    let digit = DIGITS[rng.gen_range(0..DIGITS.len())];
+                    scramble(rng, digit, &wiring)
+                })
+                .collect();
+
+            format!("{} | {}\n", patterns.join(" "), output.join(" "))
+        })
+        .collect()
+}
+
+/// Rewires each segment of the digit and shuffles the order they're listed in.
+fn scramble<R: Rng>(rng: &mut R, digit: &str, wiring: &[char]) -> String {
+    let mut segments: Vec<char> = digit
+        .bytes()
+        .map(|segment| wiring[(segment - b'a') as usize])
+        .collect();
+    segments.shuffle(rng);
+
+    segments.into_iter().collect()
+}
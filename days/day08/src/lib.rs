@@ -0,0 +1,83 @@
+use common::{Day, Diagnostic};
+use model::{solve as solve_line, Solver};
+use parser::{Input, Line};
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+pub mod generate;
+pub mod model;
+pub mod parser;
+pub mod pattern;
+pub mod tables;
+
+/// Parses each line of the input on the rayon thread pool.
+pub fn parse_parallel(buf: &str) -> Result<Input, Diagnostic> {
+    let lines: Vec<&str> = buf.lines().collect();
+
+    Ok(Input::new(
+        lines
+            .par_iter()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| l.parse().map_err(|e: Diagnostic| e.below(i)))
+            .collect::<Result<Vec<Line>, Diagnostic>>()?,
+    ))
+}
+
+pub fn part_1(input: &Input) -> usize {
+    input
+        .outputs()
+        .flat_map(|p| p.iter())
+        .filter(|&s| s.possible_numbers().len() == 1)
+        .count()
+}
+
+pub fn part_2(input: &Input) -> usize {
+    let mut solver = Solver::default();
+    input
+        .lines()
+        .map(|line| {
+            solver.reset();
+            solve_line(line, &mut solver).expect("advent of code input is well formed")
+        })
+        .sum()
+}
+
+pub fn par_part_1(input: &Input) -> usize {
+    input
+        .par_outputs()
+        .flat_map(|p| p.par_iter())
+        .filter(|p| p.possible_numbers().len() == 1)
+        .count()
+}
+
+pub fn par_part_2(input: &Input) -> usize {
+    input
+        .par_lines()
+        .map_init(Solver::default, |s, l| {
+            s.reset();
+            solve_line(l, s).expect("advent of code input is well formed")
+        })
+        .sum()
+}
+
+pub struct Day08;
+
+impl Day for Day08 {
+    const DAY: usize = 8;
+
+    type Input = parser::Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(input.parse()?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        Ok(crate::part_1(input))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        Ok(crate::part_2(input))
+    }
+}
@@ -1,27 +1,15 @@
-use std::{fs::File, io::Read, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
-use model::Solver;
-use nom::error::Error;
-use parser::{Input, Line};
-use rayon::{
-    iter::{IntoParallelRefIterator, ParallelIterator},
-    str::ParallelString,
-};
-
-use crate::model::solve;
-
-mod model;
-mod parser;
-mod pattern;
-mod tables;
+use common::input;
+use day08::{par_part_1, par_part_2, parse_parallel, parser::Input, part_1, part_2};
 
 #[derive(Debug, Parser)]
 /// Implements a solution to the 8th day of Advent of Code 2021.
 struct Options {
     #[clap()]
-    /// Path to the file that contains the input.
-    input: PathBuf,
+    /// Path to the file that contains the input. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
 
     #[clap(long)]
     /// Whether or not to run part_2
@@ -41,9 +29,7 @@ impl Options {
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let opts = Options::parse();
-    let mut buf = String::new();
-
-    File::open(&opts.input)?.read_to_string(&mut buf)?;
+    let buf = input::read_to_string(opts.input.as_deref())?;
 
     if opts.parallel {
         run_parallel(&opts, buf)
@@ -55,54 +41,21 @@ fn main() -> color_eyre::Result<()> {
 fn run_serial(opts: &Options, buf: String) -> color_eyre::Result<()> {
     let input: Input = buf.parse()?;
     if opts.part_1() {
-        let part_1 = input
-            .outputs()
-            .flat_map(|p| p.iter())
-            .filter(|&s| s.possible_numbers().len() == 1)
-            .count();
-
-        println!("part 1: {}", part_1);
+        println!("part 1: {}", part_1(&input));
     } else {
-        let mut solver = Solver::default();
-        let sum: usize = input
-            .lines()
-            .map(|line| {
-                solver.reset();
-                solve(line, &mut solver).expect("advent of code input is well formed")
-            })
-            .sum();
-
-        println!("part 2: {}", sum);
+        println!("part 2: {}", part_2(&input));
     }
 
     Ok(())
 }
 
 fn run_parallel(opts: &Options, buf: String) -> color_eyre::Result<()> {
-    let input = Input::new(
-        buf.par_lines()
-            .map(|l| l.parse())
-            .collect::<Result<Vec<Line>, Error<String>>>()?,
-    );
+    let input = parse_parallel(&buf)?;
 
     if opts.part_1() {
-        let part_1 = input
-            .par_outputs()
-            .flat_map(|p| p.par_iter())
-            .filter(|p| p.possible_numbers().len() == 1)
-            .count();
-
-        println!("part 1: {}", part_1);
+        println!("part 1: {}", par_part_1(&input));
     } else {
-        let part_2: usize = input
-            .par_lines()
-            .map_init(Solver::default, |s, l| {
-                s.reset();
-                solve(l, s).expect("advent of code input is well formed")
-            })
-            .sum();
-
-        println!("part 2: {}", part_2);
+        println!("part 2: {}", par_part_2(&input));
     }
 
     Ok(())
@@ -60,7 +60,7 @@ impl Solver {
         *self = Self::default();
     }
 
-    pub fn add(&mut self, pattern: Pattern) -> Option<Solution> {
+    pub fn add(&mut self, pattern: Pattern) -> Option<Solution<'_>> {
         let complete = self.completion_mask();
         self.propagate_constraints(pattern);
         self.flatten_to(complete);
@@ -86,14 +86,14 @@ impl Solver {
         }
     }
 
-    fn return_solution_if_done(&mut self) -> Option<Solution> {
+    fn return_solution_if_done(&mut self) -> Option<Solution<'_>> {
         self.solutions
             .iter()
             .all(Pattern::is_done)
             .then(|| self.solution())
     }
 
-    fn solution(&mut self) -> Solution {
+    fn solution(&mut self) -> Solution<'_> {
         for (l, r) in self.solutions.iter().zip(self.solution.iter_mut()) {
             let d = 1
                 << l.indicies()
@@ -1,18 +1,22 @@
 use std::str::FromStr;
 
+use common::Diagnostic;
 use nom::{
-    character::complete::{alpha1, char, line_ending, space1},
-    combinator::{map, map_res},
-    error::Error,
-    multi::{count, many0, separated_list0, separated_list1},
+    character::complete::{alpha1, char, space1},
+    combinator::{eof, map_res},
+    error::{context, VerboseError},
+    multi::{count, separated_list1},
     sequence::{separated_pair, terminated},
-    Finish, IResult,
+    Finish,
 };
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use thiserror::Error;
 
 use crate::pattern::Pattern;
 
-#[derive(Debug)]
+type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+#[derive(Debug, PartialEq, Eq)]
 pub struct Line {
     patterns: Vec<Pattern>,
     output: Vec<Pattern>,
@@ -28,30 +32,41 @@ impl Line {
     }
 }
 
-impl From<(Vec<Pattern>, Vec<Pattern>)> for Line {
-    fn from((patterns, output): (Vec<Pattern>, Vec<Pattern>)) -> Self {
-        assert_eq!(
-            10,
-            patterns.len(),
-            "advent of code specified that there would be 10 patterns"
-        );
-        assert_eq!(
-            4,
-            output.len(),
-            "advent of code specified that there would be 4 outputs"
-        );
-        Line { patterns, output }
+#[derive(Debug, Error)]
+pub enum LineError {
+    #[error("advent of code specified that there would be 10 patterns, but there were {0}")]
+    WrongNumberOfPatterns(usize),
+
+    #[error("advent of code specified that there would be 4 outputs, but there were {0}")]
+    WrongNumberOfOutputs(usize),
+}
+
+impl TryFrom<(Vec<Pattern>, Vec<Pattern>)> for Line {
+    type Error = LineError;
+
+    fn try_from((patterns, output): (Vec<Pattern>, Vec<Pattern>)) -> Result<Self, Self::Error> {
+        if patterns.len() != 10 {
+            return Err(LineError::WrongNumberOfPatterns(patterns.len()));
+        }
+
+        if output.len() != 4 {
+            return Err(LineError::WrongNumberOfOutputs(output.len()));
+        }
+
+        Ok(Line { patterns, output })
     }
 }
 
 impl FromStr for Line {
-    type Err = Error<String>;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        line(s)
+        let s = s.trim_end();
+
+        terminated(line, context("the end of the line", eof))(s)
             .finish()
             .map(|(_, l)| l)
-            .map_err(|e| Error::new(e.input.to_string(), e.code))
+            .map_err(|e| Diagnostic::from_nom(s, e))
     }
 }
 
@@ -61,12 +76,10 @@ pub struct Input {
 }
 
 impl FromStr for Input {
-    type Err = Error<String>;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse(s)
-            .map(|lines| Input { lines })
-            .map_err(|e| Error::new(e.input.to_string(), e.code))
+        parse(s).map(|lines| Input { lines })
     }
 }
 
@@ -92,30 +105,108 @@ impl Input {
     }
 }
 
-fn signal_pattern(input: &str) -> IResult<&str, Pattern> {
+fn signal_pattern(input: &str) -> IResult<'_, Pattern> {
     map_res(alpha1, |s: &str| s.parse())(input)
 }
 
-fn bar(input: &str) -> IResult<&str, char> {
-    terminated(char('|'), space1)(input)
+fn bar(input: &str) -> IResult<'_, char> {
+    context("`|` after the 10 patterns", terminated(char('|'), space1))(input)
 }
 
-fn signal_patterns(input: &str) -> IResult<&str, Vec<Pattern>> {
-    count(terminated(signal_pattern, space1), 10)(input)
+fn signal_patterns(input: &str) -> IResult<'_, Vec<Pattern>> {
+    context(
+        "10 patterns before `|`",
+        count(terminated(signal_pattern, space1), 10),
+    )(input)
 }
 
-fn output(input: &str) -> IResult<&str, Vec<Pattern>> {
-    separated_list1(space1, signal_pattern)(input)
+fn output(input: &str) -> IResult<'_, Vec<Pattern>> {
+    context(
+        "4 output patterns after `|`",
+        separated_list1(space1, signal_pattern),
+    )(input)
 }
 
-fn line(input: &str) -> IResult<&str, Line> {
-    map(separated_pair(signal_patterns, bar, output), Line::from)(input)
+fn line(input: &str) -> IResult<'_, Line> {
+    context(
+        "10 patterns, then `|`, then 4 output patterns",
+        map_res(separated_pair(signal_patterns, bar, output), Line::try_from),
+    )(input)
 }
 
-fn lines(input: &str) -> IResult<&str, Vec<Line>> {
-    separated_list0(many0(line_ending), line)(input)
+/// Parses every line of the input, skipping blank lines.
+pub fn parse(input: &str) -> Result<Vec<Line>, Diagnostic> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| l.parse().map_err(|e: Diagnostic| e.below(i)))
+        .collect()
 }
 
-pub fn parse(input: &str) -> Result<Vec<Line>, Error<&str>> {
-    lines(input).finish().map(|(_, lines)| lines)
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::{parse, Line, LineError};
+    use crate::pattern::Pattern;
+
+    fn patterns(n: usize) -> impl Strategy<Value = Vec<Pattern>> {
+        vec((1..=Pattern::MASK).prop_map(Pattern::new), n)
+    }
+
+    fn lines() -> impl Strategy<Value = Vec<Line>> {
+        vec(
+            (patterns(10), patterns(4)).prop_map(|(patterns, output)| Line { patterns, output }),
+            0..20,
+        )
+    }
+
+    fn render(lines: &[Line]) -> String {
+        let join = |patterns: &[Pattern]| {
+            patterns
+                .iter()
+                .map(Pattern::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        lines
+            .iter()
+            .map(|line| format!("{} | {}\n", join(line.patterns()), join(line.output())))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(lines in lines()) {
+            prop_assert_eq!(&lines, &parse(&render(&lines)).unwrap());
+        }
+
+        #[test]
+        fn test_does_not_panic(input in "\\PC*") {
+            let _ = parse(&input);
+        }
+    }
+
+    #[test]
+    fn test_wrong_number_of_outputs() {
+        let patterns = (1..=10).map(Pattern::new).collect::<Vec<_>>();
+        let output = (1..=3).map(Pattern::new).collect::<Vec<_>>();
+
+        assert!(matches!(
+            Line::try_from((patterns, output)),
+            Err(LineError::WrongNumberOfOutputs(3))
+        ));
+        assert!("a b c d e f g ab ac ad | a b c".parse::<Line>().is_err());
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let diagnostic =
+            parse("a b c d e f g ab ac ad | a b c d\na b c d e f g ab ac | a b c d\n").unwrap_err();
+
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 21));
+        assert_eq!(diagnostic.expected, "10 patterns before `|`");
+    }
 }
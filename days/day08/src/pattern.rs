@@ -30,17 +30,15 @@ impl FromStr for Pattern {
                 'a'..='g' => Ok(1 << char_index(c)),
                 bad_character => Err(InvalidCharacter { bad_character }),
             })
-            .fold(Ok(0), |l, r| {
-                l.and_then(|l| r.map(|r| (l, r))).map(|(l, r)| l | r)
-            })?;
+            .try_fold(0, |l, r| r.map(|r| l | r))?;
 
         Ok(Self(b))
     }
 }
 
-impl ToString for Pattern {
-    fn to_string(&self) -> String {
-        self.chars().collect()
+impl std::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.chars().collect::<String>())
     }
 }
 
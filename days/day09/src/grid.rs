@@ -1,87 +1,56 @@
 use std::str::FromStr;
 
+use common::Diagnostic;
+use grid::{Neighborhood, Point};
 use partitions::{partition_vec::AllSets, PartitionVec};
-use thiserror::Error;
-
-use crate::iter::LocationIter;
 
 #[derive(Debug)]
 pub struct Grid {
-    x: usize,
-    data: PartitionVec<u8>,
+    heights: grid::Grid<u8>,
+    basins: PartitionVec<u8>,
 }
 
 impl Grid {
-    fn build(data: PartitionVec<u8>, x: usize) -> Self {
-        let mut s = Self { data, x };
-
-        // TODO: it's definitely possible to remove this queue. Just need a little rearchitecting.
-        let mut queue = Vec::new();
+    fn build(heights: grid::Grid<u8>) -> Self {
+        let mut basins: PartitionVec<u8> = heights.cells().iter().copied().collect();
 
-        for location in s.locations().filter(|l| l.item() != 9) {
-            for neighbor in location
-                .neighbors(&mut location.default_buffer())
-                .iter()
-                .filter(|l| l.item() != 9)
+        for (point, _) in heights.iter().filter(|(_, &height)| height != 9) {
+            for neighbor in heights
+                .neighbors(point, Neighborhood::Four)
+                .filter(|&n| heights[n] != 9)
             {
-                queue.push((location.index(), neighbor.index()));
+                basins.union(heights.index_of(point), heights.index_of(neighbor));
             }
         }
 
-        for (l, r) in queue {
-            s.data.union(l, r);
-        }
-
-        s
-    }
-
-    pub fn basins(&self) -> AllSets<u8> {
-        self.data.all_sets()
+        Self { heights, basins }
     }
 
-    pub fn size_x(&self) -> usize {
-        self.x
+    pub fn basins(&self) -> AllSets<'_, u8> {
+        self.basins.all_sets()
     }
 
-    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
-        self.data.get(y * self.x + x).copied()
+    pub fn heights(&self) -> &grid::Grid<u8> {
+        &self.heights
     }
 
-    pub fn locations(&self) -> LocationIter {
-        LocationIter::new(self)
+    /// The points which are lower than every one of their neighbours, along with their heights.
+    pub fn low_points(&self) -> impl Iterator<Item = (Point, u8)> + '_ {
+        self.heights
+            .iter()
+            .filter(|&(point, &height)| {
+                self.heights
+                    .neighbors(point, Neighborhood::Four)
+                    .all(|n| self.heights[n] > height)
+            })
+            .map(|(point, &height)| (point, height))
     }
 }
 
 impl FromStr for Grid {
-    type Err = ParseGridError;
+    type Err = Diagnostic;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let data = s
-            .lines()
-            .flat_map(|line| {
-                line.chars().map(|c| {
-                    c.to_digit(10)
-                        .map(|d| d as u8)
-                        .ok_or(ParseGridError::BadInt(c))
-                })
-            })
-            .collect::<Result<PartitionVec<u8>, ParseGridError>>()?;
-
-        let x = s
-            .lines()
-            .next()
-            .ok_or(ParseGridError::BadDimensions)?
-            .chars()
-            .count();
-
-        Ok(Self::build(data, x))
+        Ok(Self::build(s.parse()?))
     }
 }
-
-#[derive(Debug, Error)]
-pub enum ParseGridError {
-    #[error("{0} is not a valid digit")]
-    BadInt(char),
-    #[error("grid did not have even dimensions")]
-    BadDimensions,
-}
@@ -0,0 +1,48 @@
+use common::Day;
+
+use crate::grid::Grid;
+
+pub mod generate;
+pub mod grid;
+
+pub fn part_1(grid: &Grid) -> usize {
+    // the risk level of a low point is one more than its height
+    grid.low_points()
+        .map(|(_, height)| height as usize + 1)
+        .sum()
+}
+
+pub fn part_2(grid: &Grid) -> usize {
+    let mut highest = [0; 4];
+
+    for size in grid.basins().map(|s| s.count()) {
+        *highest.first_mut().unwrap() = size;
+        highest.sort_unstable();
+    }
+
+    let top_three = &highest[1..];
+
+    top_three.iter().product()
+}
+
+pub struct Day09;
+
+impl Day for Day09 {
+    const DAY: usize = 9;
+
+    type Input = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(input.parse()?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        Ok(crate::part_1(input))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        Ok(crate::part_2(input))
+    }
+}
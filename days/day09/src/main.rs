@@ -1,18 +1,15 @@
-use std::{fs::File, io::Read, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
-use grid::Grid;
-
-mod grid;
-mod iter;
-mod location;
+use common::{input, Day};
+use day09::Day09;
 
 #[derive(Debug, Parser)]
 /// Implements a solution to the 9th day of Advent of Code 2021.
 struct Options {
     #[clap()]
-    /// Path to the file that contains the input.
-    input: PathBuf,
+    /// Path to the file that contains the input. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
 
     #[clap(long)]
     /// Whether or not to run part_2
@@ -28,37 +25,15 @@ impl Options {
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let opts = Options::parse();
-    let mut buf = String::new();
-
-    File::open(&opts.input)?.read_to_string(&mut buf)?;
+    let buf = input::read_to_string(opts.input.as_deref())?;
 
-    let grid = buf.parse()?;
+    let grid = Day09::parse(&buf)?;
 
     if opts.part_1() {
-        println!("part 1: {}", part_1(grid));
+        println!("part 1: {}", Day09::part_1(&grid)?);
     } else {
-        println!("part 2: {}", part_2(grid));
+        println!("part 2: {}", Day09::part_2(&grid)?);
     }
 
     Ok(())
 }
-
-fn part_1(grid: Grid) -> usize {
-    grid.locations()
-        .filter(|l| l.is_low_point())
-        .map(|l| l.risk() as usize)
-        .sum()
-}
-
-fn part_2(grid: Grid) -> usize {
-    let mut highest = [0; 4];
-
-    for size in grid.basins().map(|s| s.count()) {
-        *highest.first_mut().unwrap() = size;
-        highest.sort_unstable();
-    }
-
-    let top_three = &highest[1..];
-
-    top_three.iter().product()
-}
@@ -0,0 +1,55 @@
+use rand::Rng;
+
+/// How many lines the official navigation subsystem has.
+pub const DEFAULT_SIZE: usize = 90;
+
+const PAIRS: [(char, char); 4] = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+/// The autocomplete score grows fivefold with every delimiter left open, so more than this would
+/// overflow it.
+const MAX_DEPTH: usize = 20;
+
+/// Generates `size` lines (at least one) that are each either corrupted or incomplete. There's
+/// always an odd number of incomplete lines so that part 2 has a middle score.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut corrupted: Vec<bool> = (0..size.max(1)).map(|_| rng.gen_bool(0.5)).collect();
+
+    if corrupted.iter().filter(|&&c| !c).count() % 2 == 0 {
+        corrupted[0] = !corrupted[0];
+    }
+
+    corrupted.into_iter().map(|c| line(rng, c)).collect()
+}
+
+fn line<R: Rng>(rng: &mut R, corrupted: bool) -> String {
+    let mut line = String::new();
+    let mut expected = Vec::new();
+
+    for _ in 0..rng.gen_range(20..100) {
+        if expected.is_empty() || (expected.len() < MAX_DEPTH && rng.gen_bool(0.6)) {
+            let (open, close) = PAIRS[rng.gen_range(0..PAIRS.len())];
+            line.push(open);
+            expected.push(close);
+        } else {
+            line.push(expected.pop().unwrap());
+        }
+    }
+
+    if expected.is_empty() {
+        let (open, close) = PAIRS[rng.gen_range(0..PAIRS.len())];
+        line.push(open);
+        expected.push(close);
+    }
+
+    if corrupted {
+        let wrong: Vec<_> = PAIRS
+            .iter()
+            .map(|&(_, close)| close)
+            .filter(|close| Some(close) != expected.last())
+            .collect();
+        line.push(wrong[rng.gen_range(0..wrong.len())]);
+    }
+
+    line.push('\n');
+    line
+}
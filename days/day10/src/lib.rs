@@ -0,0 +1,80 @@
+use command::Command;
+use common::{Day, Diagnostic};
+use interpreter::Interpreter;
+
+pub mod command;
+pub mod delimeter;
+pub mod generate;
+pub mod interpreter;
+
+pub fn part_1(source: &str) -> usize {
+    Interpreter::new(Command::stream(source))
+        .filter_map(Result::err)
+        .filter(|e| e.is_corrupted())
+        .filter_map(|e| e.score())
+        .sum()
+}
+
+pub fn part_2(source: &str) -> usize {
+    let mut results: Vec<usize> = Interpreter::new(Command::stream(source))
+        .filter_map(Result::err)
+        .filter(|e| e.is_incomplete())
+        .filter_map(|e| e.score())
+        .collect();
+
+    results.sort_unstable();
+    results[results.len() / 2]
+}
+
+/// Checks that `source` is only made up of brackets, pointing at the first thing that isn't one.
+pub fn validate(source: &str) -> Result<(), Diagnostic> {
+    match source.find(|c| !"()[]{}<>\n".contains(c)) {
+        Some(offset) => Err(Diagnostic::at_offset(
+            source,
+            offset,
+            "one of `(`, `)`, `[`, `]`, `{`, `}`, `<` or `>`",
+        )),
+        None => Ok(()),
+    }
+}
+
+pub struct Day10;
+
+impl Day for Day10 {
+    const DAY: usize = 10;
+
+    // the interpreter streams commands straight out of the source, so there's nothing to parse ahead of time
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        validate(input)?;
+        Ok(input.to_string())
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        Ok(crate::part_1(input))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        Ok(crate::part_2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{part_1, part_2};
+
+    #[test]
+    fn test_part_1() {
+        let input = include_str!("../input/test.txt");
+        assert_eq!(26397, part_1(input));
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = include_str!("../input/test.txt");
+        assert_eq!(288957, part_2(input));
+    }
+}
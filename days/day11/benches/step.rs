@@ -0,0 +1,42 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use day11::grid::Grid;
+
+const EXAMPLE: &str = include_str!("../input/testinput.txt");
+
+/// Tiles the example `factor` times in each direction.
+fn scale(input: &str, factor: usize) -> String {
+    input
+        .lines()
+        .map(|line| line.repeat(factor) + "\n")
+        .collect::<String>()
+        .repeat(factor)
+}
+
+/// Runs a single step of the simulation from the initial grid.
+fn step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day11/step");
+
+    for factor in [1, 10] {
+        let grid: Grid = scale(EXAMPLE, factor).parse().expect("example must parse");
+        let size = 10 * factor;
+
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", size, size)),
+            &grid,
+            |b, grid| {
+                b.iter_batched_ref(
+                    || grid.clone(),
+                    |g| black_box(g.step()),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, step);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+use rand::Rng;
+
+/// How wide and tall the official grid is.
+pub const DEFAULT_SIZE: usize = 10;
+
+/// Generates a `size` by `size` grid of octopus energy levels.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row: String = (0..size)
+                .map(|_| char::from_digit(rng.gen_range(0..=9), 10).unwrap())
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
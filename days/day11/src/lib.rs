@@ -0,0 +1,28 @@
+use common::Day;
+
+use crate::grid::Grid;
+
+pub mod generate;
+pub mod grid;
+
+pub struct Day11;
+
+impl Day for Day11 {
+    const DAY: usize = 11;
+
+    type Input = Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(input.parse()?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        Ok(input.clone().step_n(100))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        Ok(input.clone().first_synchronization())
+    }
+}
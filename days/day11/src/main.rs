@@ -1,16 +1,15 @@
-use std::{fs::File, io::Read, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
-use grid::Grid;
-
-mod grid;
+use common::{input, Day};
+use day11::Day11;
 
 #[derive(Debug, Parser)]
 /// Implements a solution to the 11th day of Advent of Code 2021.
 struct Options {
     #[clap()]
-    /// Path to the file that contains the input.
-    input: PathBuf,
+    /// Path to the file that contains the input. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
 
     #[clap(long)]
     /// whether or not to run part 2
@@ -20,16 +19,14 @@ struct Options {
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let opts = Options::parse();
-    let mut buf = String::new();
-
-    File::open(&opts.input)?.read_to_string(&mut buf)?;
+    let buf = input::read_to_string(opts.input.as_deref())?;
 
-    let mut grid: Grid = buf.parse()?;
+    let grid = Day11::parse(&buf)?;
 
     if opts.part_2 {
-        println!("part 2 {}", grid.first_synchronization());
+        println!("part 2 {}", Day11::part_2(&grid)?);
     } else {
-        println!("part 1 {}", grid.step_n(100));
+        println!("part 1 {}", Day11::part_1(&grid)?);
     }
 
     Ok(())
@@ -0,0 +1,35 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day12::{
+    parser::Parser,
+    pathfinding::{count_all_paths, Part1, Part2},
+};
+
+/// The three examples from the puzzle grow from 10 to 226 paths for part 1 (and from 36 to 3509
+/// for part 2), which stands in for scaling the cave system.
+const EXAMPLES: [(&str, &str); 3] = [
+    ("small", include_str!("../input/small.txt")),
+    ("medium", include_str!("../input/medium.txt")),
+    ("large", include_str!("../input/large.txt")),
+];
+
+fn count_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day12/count_all_paths");
+
+    for (name, example) in EXAMPLES {
+        let parse = Parser::parse(example).expect("example must parse");
+
+        group.bench_with_input(BenchmarkId::new("part_1", name), &parse, |b, parse| {
+            b.iter(|| black_box(count_all_paths::<Part1>(parse)))
+        });
+        group.bench_with_input(BenchmarkId::new("part_2", name), &parse, |b, parse| {
+            b.iter(|| black_box(count_all_paths::<Part2>(parse)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, count_paths);
+criterion_main!(benches);
@@ -0,0 +1,71 @@
+use std::collections::BTreeSet;
+
+use rand::{seq::index, Rng};
+
+/// About how many caves the official input has, not counting `start` and `end`.
+pub const DEFAULT_SIZE: usize = 12;
+
+/// Generates a connected cave system with `size` caves besides `start` and `end`, about a third
+/// of which are large. Two large caves are never connected, as there would be infinitely many
+/// paths between them, and every cave leads somewhere other than `start`. The number of paths
+/// grows very quickly with `size`.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    // two letter names, which can never collide with `start` or `end`
+    let names = index::sample(rng, 26 * 26, size.min(26 * 26));
+    let caves: Vec<String> = names
+        .into_iter()
+        .map(|n| {
+            let name: String = [n / 26, n % 26]
+                .iter()
+                .map(|&c| (b'a' + c as u8) as char)
+                .collect();
+
+            if rng.gen_ratio(1, 3) {
+                name.to_uppercase()
+            } else {
+                name
+            }
+        })
+        .collect();
+
+    let is_large = |cave: &str| cave.chars().all(|c| c.is_uppercase());
+    let mut passages = BTreeSet::new();
+
+    // connect every cave to one or two of the caves before it, which keeps the system connected
+    for (i, cave) in caves.iter().enumerate() {
+        let candidates: Vec<&str> = std::iter::once("start")
+            .chain(caves[..i].iter().map(String::as_str))
+            .filter(|other| !(is_large(cave) && is_large(other)))
+            .collect();
+
+        for _ in 0..rng.gen_range(1..=2) {
+            let other = candidates[rng.gen_range(0..candidates.len())];
+            passages.insert((other.to_string(), cave.clone()));
+        }
+    }
+
+    // the last caves are the furthest from the start, so lead them to the end
+    for cave in caves.iter().rev().take(2) {
+        passages.insert((cave.clone(), "end".to_string()));
+    }
+
+    // the solution expects every cave to lead somewhere besides back to the start
+    for cave in &caves {
+        let leads_somewhere = passages
+            .iter()
+            .any(|(from, to)| (from == cave && to != "start") || (to == cave && from != "start"));
+
+        if !leads_somewhere {
+            passages.insert((cave.clone(), "end".to_string()));
+        }
+    }
+
+    if caves.is_empty() {
+        passages.insert(("start".to_string(), "end".to_string()));
+    }
+
+    passages
+        .into_iter()
+        .map(|(from, to)| format!("{}-{}\n", from, to))
+        .collect()
+}
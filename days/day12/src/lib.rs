@@ -0,0 +1,29 @@
+use common::Day;
+use parser::Parse;
+use pathfinding::{count_all_paths, Part1, Part2};
+
+pub mod generate;
+pub mod parser;
+pub mod pathfinding;
+
+pub struct Day12;
+
+impl Day for Day12 {
+    const DAY: usize = 12;
+
+    type Input = Parse;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(parser::Parser::parse(input)?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        Ok(count_all_paths::<Part1>(input))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        Ok(count_all_paths::<Part2>(input))
+    }
+}
@@ -1,19 +1,15 @@
-use std::{fs::File, io::Read, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
-use pathfinding::count_all_paths;
-
-use crate::pathfinding::{Part1, Part2};
-
-mod parser;
-mod pathfinding;
+use common::{input, Day};
+use day12::Day12;
 
 #[derive(Debug, Parser)]
 /// Implements a solution to the 12th day of Advent of Code 2021.
 struct Options {
     #[clap()]
-    /// Path to the file that contains the input.
-    input: PathBuf,
+    /// Path to the file that contains the input. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
 
     #[clap(long)]
     /// whether or not to run part 2
@@ -24,16 +20,14 @@ fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let opts = Options::parse();
 
-    let mut buf = String::new();
-
-    File::open(&opts.input)?.read_to_string(&mut buf)?;
+    let buf = input::read_to_string(opts.input.as_deref())?;
 
-    let input = parser::Parser::parse(&buf).expect("advent of code input is always valid");
+    let input = Day12::parse(&buf)?;
 
     if opts.part_2 {
-        println!("part 2 {}", count_all_paths::<Part2>(&input));
+        println!("part 2 {}", Day12::part_2(&input)?);
     } else {
-        println!("part 1 {}", count_all_paths::<Part1>(&input));
+        println!("part 1 {}", Day12::part_1(&input)?);
     }
 
     Ok(())
@@ -1,15 +1,18 @@
 use std::sync::atomic::{AtomicU16, Ordering};
 
+use common::Diagnostic;
 use dashmap::DashMap;
 use fxhash::FxBuildHasher;
 use nom::{
     character::complete::{alpha1, char},
-    error::Error,
-    sequence::separated_pair,
-    Finish, IResult,
+    combinator::eof,
+    error::{context, VerboseError},
+    sequence::{separated_pair, terminated},
+    Finish,
 };
-use rayon::{iter::ParallelIterator, str::ParallelString};
-use thiserror::Error;
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
 
 #[derive(Debug)]
 pub struct Parse {
@@ -46,12 +49,6 @@ impl Passage {
     }
 }
 
-#[derive(Debug, Error)]
-pub enum ParseError {
-    #[error("failed to parse input with error {0}")]
-    ParsingFailure(Error<String>),
-}
-
 #[derive(Debug, Default)]
 pub struct Parser<'a> {
     map: DashMap<&'a str, u16, FxBuildHasher>,
@@ -59,21 +56,26 @@ pub struct Parser<'a> {
 }
 
 impl<'a> Parser<'a> {
-    pub fn parse(input: &'a str) -> Result<Parse, ParseError> {
+    pub fn parse(input: &'a str) -> Result<Parse, Diagnostic> {
         Self::default().parse_str(input)
     }
 
-    pub fn parse_str(&mut self, input: &'a str) -> Result<Parse, ParseError> {
-        let data: Result<Vec<Passage>, ParseError> = input
-            .par_lines()
-            .map(Self::passage)
-            .map(|s| {
-                s.finish()
+    pub fn parse_str(&mut self, input: &'a str) -> Result<Parse, Diagnostic> {
+        let lines: Vec<&str> = input.lines().collect();
+        let data: Result<Vec<Passage>, Diagnostic> = lines
+            .par_iter()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                let line = line.trim_end();
+
+                Self::passage(line)
+                    .finish()
                     .map(|(_, (from, to))| Passage {
                         from: self.parse_cave(from),
                         to: self.parse_cave(to),
                     })
-                    .map_err(|e| ParseError::ParsingFailure(Error::new(e.to_string(), e.code)))
+                    .map_err(|e| Diagnostic::from_nom(line, e).below(i))
             })
             .collect();
 
@@ -99,11 +101,70 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn cave(input: &str) -> IResult<&str, &str> {
-        alpha1(input)
+    fn cave(input: &str) -> IResult<'_, &str> {
+        context("a cave name", alpha1)(input)
+    }
+
+    fn passage(input: &str) -> IResult<'_, (&str, &str)> {
+        terminated(
+            separated_pair(
+                Self::cave,
+                context("`-` between caves", char('-')),
+                Self::cave,
+            ),
+            context("the end of the line", eof),
+        )(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use proptest::{collection::vec, prelude::*};
+
+    use super::{Cave, Parser};
+
+    fn names() -> impl Strategy<Value = String> {
+        prop_oneof![
+            Just("start".to_string()),
+            Just("end".to_string()),
+            "[a-z]{1,3}",
+            "[A-Z]{1,3}",
+        ]
     }
 
-    fn passage(input: &str) -> IResult<&str, (&str, &str)> {
-        separated_pair(Self::cave, char('-'), Self::cave)(input)
+    proptest! {
+        /// Caves are numbered as they're parsed, so rather than comparing the numbers this checks
+        /// that each cave is the right kind, and that every name was always given the same number
+        /// and no two names share one.
+        #[test]
+        fn test_round_trip(passages in vec((names(), names()), 0..50)) {
+            let text: String = passages.iter().map(|(from, to)| format!("{}-{}\n", from, to)).collect();
+            let parse = Parser::parse(&text).unwrap();
+            prop_assert_eq!(passages.len(), parse.passages().len());
+
+            let mut codes = HashMap::new();
+            let mut names = HashMap::new();
+            let caves = parse.passages().iter().flat_map(|p| [p.from(), p.to()]);
+            let expected_names = passages.iter().flat_map(|(from, to)| [from, to]);
+
+            for (cave, name) in caves.zip(expected_names) {
+                match cave {
+                    Cave::Start => prop_assert_eq!(name, "start"),
+                    Cave::End => prop_assert_eq!(name, "end"),
+                    Cave::Large(code) | Cave::Small(code) => {
+                        prop_assert_eq!(matches!(cave, Cave::Large(_)), name.chars().all(char::is_uppercase));
+                        prop_assert_eq!(*codes.entry(name.clone()).or_insert(code), code);
+                        prop_assert_eq!(names.entry(code).or_insert_with(|| name.clone()), name);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_does_not_panic(input in "\\PC*") {
+            let _ = Parser::parse(&input);
+        }
     }
 }
@@ -0,0 +1,54 @@
+use std::collections::HashSet;
+
+use rand::Rng;
+
+/// About how many dots the official input has.
+pub const DEFAULT_SIZE: usize = 750;
+
+/// The folds of the official input, in order. Each one is along the middle of the paper left by
+/// the folds before it, which ends up 40 by 6, the size of eight letters.
+const FOLDS: [(char, usize); 12] = [
+    ('x', 655),
+    ('y', 447),
+    ('x', 327),
+    ('y', 223),
+    ('x', 163),
+    ('y', 111),
+    ('x', 81),
+    ('y', 55),
+    ('x', 40),
+    ('y', 27),
+    ('y', 13),
+    ('y', 6),
+];
+
+/// Generates up to `size` distinct dots and the official folds. The dots are placed on the folded
+/// paper and then randomly unfolded, so none of them ever lands on a fold line.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut seen = HashSet::new();
+    let mut out = String::new();
+
+    for _ in 0..size {
+        let (mut x, mut y) = (rng.gen_range(0..40), rng.gen_range(0..6));
+
+        for &(axis, line) in FOLDS.iter().rev() {
+            if rng.gen_bool(0.5) {
+                match axis {
+                    'x' => x = 2 * line - x,
+                    _ => y = 2 * line - y,
+                }
+            }
+        }
+
+        if seen.insert((x, y)) {
+            out.push_str(&format!("{},{}\n", x, y));
+        }
+    }
+
+    out.push('\n');
+    for (axis, line) in FOLDS {
+        out.push_str(&format!("fold along {}={}\n", axis, line));
+    }
+
+    out
+}
@@ -0,0 +1,33 @@
+use color_eyre::eyre::eyre;
+use common::Day;
+
+pub mod generate;
+pub mod model;
+pub mod parser;
+
+pub struct Day13;
+
+impl Day for Day13 {
+    const DAY: usize = 13;
+
+    type Input = model::Input;
+    type Part1 = usize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(parser::parse(input)?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        input
+            .clone()
+            .fold()
+            .ok_or_else(|| eyre!("there were no folds in the input"))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        let mut input = input.clone();
+        input.fold_all();
+        Ok(input.to_string())
+    }
+}
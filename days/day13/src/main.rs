@@ -1,17 +1,15 @@
-use std::{fs::File, io::Read, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
-use parser::parse;
-
-mod model;
-mod parser;
+use common::{input, Day};
+use day13::Day13;
 
 #[derive(Debug, Parser)]
 /// Implements a solution to the 13th day of Advent of Code 2021.
 struct Options {
     #[clap()]
-    /// Path to the file that contains the input.
-    input: PathBuf,
+    /// Path to the file that contains the input. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
 
     #[clap(long)]
     /// whether or not to run part 2
@@ -21,17 +19,14 @@ struct Options {
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let opts = Options::parse();
-    let mut buf = String::new();
-
-    File::open(&opts.input)?.read_to_string(&mut buf)?;
+    let buf = input::read_to_string(opts.input.as_deref())?;
 
-    let mut input = parse(&buf).expect("advent of code input is always valid");
+    let input = Day13::parse(&buf)?;
 
     if opts.part_2 {
-        input.fold_all();
-        println!("part 2\n{}", input);
+        println!("part 2\n{}", Day13::part_2(&input)?);
     } else {
-        println!("part 1 {}", input.fold().unwrap());
+        println!("part 1 {}", Day13::part_1(&input)?);
     }
 
     Ok(())
@@ -12,6 +12,7 @@ impl From<(usize, usize)> for Point {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Fold {
     Y(usize),
     X(usize),
@@ -31,6 +32,7 @@ impl Fold {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Input {
     folds: Vec<Fold>,
     from: HashSet<Point>,
@@ -1,54 +1,130 @@
+use common::Diagnostic;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{char, digit1, line_ending},
-    combinator::{map, map_res},
-    error::Error,
+    character::complete::{char, digit1, line_ending, multispace0},
+    combinator::{cut, eof, map, map_res},
+    error::{context, VerboseError},
     multi::{many1, separated_list1},
-    sequence::{preceded, separated_pair},
-    Finish, IResult,
+    sequence::{preceded, separated_pair, terminated},
+    Finish,
 };
 
 use crate::model::{Fold, Input, Point};
 
-pub fn parse(input: &str) -> Result<Input, Error<&str>> {
-    all_input(input).finish().map(|(_, i)| i)
+type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+pub fn parse(input: &str) -> Result<Input, Diagnostic> {
+    all_input(input)
+        .finish()
+        .map(|(_, i)| i)
+        .map_err(|e| Diagnostic::from_nom(input, e))
 }
 
-fn number(input: &str) -> IResult<&str, usize> {
-    map_res(digit1, |d: &str| d.parse())(input)
+fn number(input: &str) -> IResult<'_, usize> {
+    context("a number", map_res(digit1, |d: &str| d.parse()))(input)
 }
 
-fn point(input: &str) -> IResult<&str, Point> {
-    map(separated_pair(number, char(','), number), Point::from)(input)
+fn point(input: &str) -> IResult<'_, Point> {
+    map(
+        separated_pair(
+            number,
+            context("`,` between coordinates", char(',')),
+            number,
+        ),
+        Point::from,
+    )(input)
 }
 
-fn points(input: &str) -> IResult<&str, Vec<Point>> {
+fn points(input: &str) -> IResult<'_, Vec<Point>> {
     separated_list1(line_ending, point)(input)
 }
 
-fn fold(input: &str) -> IResult<&str, Fold> {
-    map(
-        separated_pair(alt((char('x'), char('y'))), char('='), number),
-        |(dim, num)| match dim {
-            'x' => Fold::X(num),
-            'y' => Fold::Y(num),
-            other => panic!("unexpected dimension {}", other), // TODO(dan) remove this panic
-        },
+fn fold(input: &str) -> IResult<'_, Fold> {
+    context(
+        "`x=` or `y=` followed by a number",
+        alt((
+            map(preceded(tag("x="), number), Fold::X),
+            map(preceded(tag("y="), number), Fold::Y),
+        )),
     )(input)
 }
 
-fn fold_line(input: &str) -> IResult<&str, Fold> {
-    preceded(tag("fold along "), fold)(input)
+fn fold_line(input: &str) -> IResult<'_, Fold> {
+    preceded(tag("fold along "), cut(fold))(input)
 }
 
-fn folds(input: &str) -> IResult<&str, Vec<Fold>> {
-    separated_list1(line_ending, fold_line)(input)
+fn folds(input: &str) -> IResult<'_, Vec<Fold>> {
+    context(
+        "a `fold along` instruction",
+        separated_list1(line_ending, fold_line),
+    )(input)
 }
 
-fn all_input(input: &str) -> IResult<&str, Input> {
+fn all_input(input: &str) -> IResult<'_, Input> {
     map(
-        separated_pair(points, many1(line_ending), folds),
+        terminated(
+            separated_pair(
+                points,
+                context(
+                    "a blank line between the dots and the folds",
+                    many1(line_ending),
+                ),
+                folds,
+            ),
+            context(
+                "another fold or the end of the input",
+                preceded(multispace0, eof),
+            ),
+        ),
         Input::from,
     )(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::parse;
+    use crate::model::{Fold, Input, Point};
+
+    fn folds() -> impl Strategy<Value = Fold> {
+        prop_oneof![
+            (0..2000usize).prop_map(Fold::X),
+            (0..2000usize).prop_map(Fold::Y)
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(
+            points in vec((0..2000usize, 0..2000usize), 1..50),
+            folds in vec(folds(), 1..10),
+        ) {
+            let mut text: String = points.iter().map(|(x, y)| format!("{},{}\n", x, y)).collect();
+            text.push('\n');
+            for fold in &folds {
+                match fold {
+                    Fold::X(x) => text.push_str(&format!("fold along x={}\n", x)),
+                    Fold::Y(y) => text.push_str(&format!("fold along y={}\n", y)),
+                }
+            }
+
+            let points = points.into_iter().map(Point::from).collect();
+            prop_assert_eq!(Input::from((points, folds)), parse(&text).unwrap());
+        }
+
+        #[test]
+        fn test_does_not_panic(input in "\\PC*") {
+            let _ = parse(&input);
+        }
+    }
+
+    #[test]
+    fn test_unknown_fold_dimension() {
+        let diagnostic = parse("1,2\n\nfold along z=4\n").unwrap_err();
+
+        assert_eq!((diagnostic.line, diagnostic.column), (3, 12));
+        assert_eq!(diagnostic.expected, "`x=` or `y=` followed by a number");
+    }
+}
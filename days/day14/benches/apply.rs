@@ -0,0 +1,37 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use day14::{parser, replacer::Replacer};
+
+const EXAMPLE: &str = include_str!("../input/test.txt");
+
+/// Runs a single insertion step, and the 40 steps of part 2, on the example polymer and on the
+/// example polymer repeated into a much longer one.
+fn apply(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day14/apply");
+    let mut input = parser::parse(EXAMPLE).expect("example must parse");
+    let example_polymer = input.polymer.clone();
+
+    for copies in [1, 1000] {
+        input.polymer = example_polymer.repeat(copies);
+        let len = input.polymer.len();
+
+        group.bench_with_input(BenchmarkId::new("once", len), &input, |b, input| {
+            b.iter_batched_ref(
+                || Replacer::new(input),
+                |r| r.apply(),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("40_times", len), &input, |b, input| {
+            b.iter_batched_ref(
+                || Replacer::new(input),
+                |r| r.apply_n(40),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, apply);
+criterion_main!(benches);
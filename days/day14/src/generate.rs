@@ -0,0 +1,24 @@
+use rand::Rng;
+
+/// How long the official polymer template is.
+pub const DEFAULT_SIZE: usize = 20;
+
+/// The elements used by the official input.
+const ELEMENTS: &[u8] = b"BCFHKNOPSV";
+
+/// Generates a polymer template `size` elements long, and an insertion rule for every pair of
+/// elements.
+pub fn generate<R: Rng>(rng: &mut R, size: usize) -> String {
+    let mut element = || ELEMENTS[rng.gen_range(0..ELEMENTS.len())] as char;
+
+    let mut out: String = (0..size).map(|_| element()).collect();
+    out.push_str("\n\n");
+
+    for &l in ELEMENTS {
+        for &r in ELEMENTS {
+            out.push_str(&format!("{}{} -> {}\n", l as char, r as char, element()));
+        }
+    }
+
+    out
+}
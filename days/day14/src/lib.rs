@@ -0,0 +1,32 @@
+use common::Day;
+use replacer::Replacer;
+
+pub mod generate;
+pub mod parser;
+pub mod replacer;
+
+pub struct Day14;
+
+impl Day for Day14 {
+    const DAY: usize = 14;
+
+    type Input = parser::Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(parser::parse(input)?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        let mut replacer = Replacer::new(input);
+        replacer.apply_n(10);
+        Ok(replacer.frequency_diff())
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        let mut replacer = Replacer::new(input);
+        replacer.apply_n(40);
+        Ok(replacer.frequency_diff())
+    }
+}
@@ -1,18 +1,15 @@
-use std::{fs::File, io::Read, path::PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
-use parser::parse;
-use replacer::Replacer;
-
-mod parser;
-mod replacer;
+use common::{input, Day};
+use day14::Day14;
 
 #[derive(Debug, Parser)]
 /// Implements a solution to the 14th day of Advent of Code 2021.
 struct Options {
     #[clap()]
-    /// Path to the file that contains the input.
-    input: PathBuf,
+    /// Path to the file that contains the input. Reads from stdin if omitted or `-`.
+    input: Option<PathBuf>,
 
     #[clap(long)]
     /// whether or not to run part 2
@@ -22,19 +19,12 @@ struct Options {
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     let opts = Options::parse();
-    let mut buf = String::new();
-
-    File::open(&opts.input)?.read_to_string(&mut buf)?;
-
-    let input = parse(&buf)?;
-
-    let mut replacer = Replacer::new(&input);
+    let buf = input::read_to_string(opts.input.as_deref())?;
 
-    replacer.apply_n(10);
-    println!("part 1 {}", replacer.frequency_diff());
+    let input = Day14::parse(&buf)?;
 
-    replacer.apply_n(30);
-    println!("part 2 {}", replacer.frequency_diff());
+    println!("part 1 {}", Day14::part_1(&input)?);
+    println!("part 2 {}", Day14::part_2(&input)?);
 
     Ok(())
 }
@@ -1,81 +1,104 @@
 use std::collections::HashMap;
 
+use common::Diagnostic;
 use nom::{
     bytes::complete::tag,
-    character::complete::{alpha1, line_ending, space0},
-    combinator::map_res,
+    character::complete::{alpha1, line_ending, multispace0, space0},
+    combinator::{eof, map_res},
+    error::{context, VerboseError},
     multi::{many0, separated_list1},
     sequence::{preceded, separated_pair, terminated},
-    AsBytes, Finish, IResult,
+    AsBytes, Finish,
 };
 use thiserror::Error;
 
-pub struct Input<'a> {
-    pub polymer: &'a [u8],
+type IResult<'a, T> = nom::IResult<&'a str, T, VerboseError<&'a str>>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    pub polymer: Vec<u8>,
     pub replacement_rule: HashMap<(u8, u8), u8>,
 }
 
-pub fn parse(input: &str) -> Result<Input, ParseError> {
+pub fn parse(input: &str) -> Result<Input, Diagnostic> {
     full_file(input)
         .finish()
         .map(|(_, (polymer, replacements))| Input {
-            polymer,
+            polymer: polymer.to_vec(),
             replacement_rule: replacements.into_iter().collect(),
         })
-        .map_err(|e| ParseError::ParseError(nom::error::Error::new(e.input.to_string(), e.code)))
+        .map_err(|e| Diagnostic::from_nom(input, e))
 }
 
 type File<'a> = (&'a [u8], Vec<((u8, u8), u8)>);
 
-fn full_file(input: &str) -> IResult<&str, File> {
-    separated_pair(
-        polymer,
-        many0(line_ending),
-        separated_list1(line_ending, replacement_rule),
+fn full_file(input: &str) -> IResult<'_, File<'_>> {
+    terminated(
+        separated_pair(
+            polymer,
+            many0(line_ending),
+            separated_list1(line_ending, replacement_rule),
+        ),
+        context(
+            "another insertion rule or the end of the input",
+            preceded(multispace0, eof),
+        ),
     )(input)
 }
 
-fn polymer(input: &str) -> IResult<&str, &[u8]> {
-    map_res(alpha1, |r: &str| {
-        r.is_ascii()
-            .then(|| r.as_bytes())
-            .ok_or(ParseError::NonAsciiInput)
-    })(input)
+fn polymer(input: &str) -> IResult<'_, &[u8]> {
+    context(
+        "the polymer template",
+        map_res(alpha1, |r: &str| {
+            r.is_ascii()
+                .then_some(r.as_bytes())
+                .ok_or(ParseError::NonAsciiInput)
+        }),
+    )(input)
 }
 
-fn left_hand_side(input: &str) -> IResult<&str, (u8, u8)> {
-    map_res(alpha1, |r: &str| {
-        r.is_ascii()
-            .then(|| r.as_bytes())
-            .ok_or(ParseError::NonAsciiInput)
-            .and_then(|s| {
-                (s.len() == 2)
-                    .then(|| s.as_bytes())
-                    .map(|bytes| (bytes[0], bytes[1]))
-                    .ok_or(ParseError::TooManyLHSSymbols)
-            })
-    })(input)
+fn left_hand_side(input: &str) -> IResult<'_, (u8, u8)> {
+    context(
+        "a pair of elements",
+        map_res(alpha1, |r: &str| {
+            r.is_ascii()
+                .then_some(r.as_bytes())
+                .ok_or(ParseError::NonAsciiInput)
+                .and_then(|s| {
+                    (s.len() == 2)
+                        .then(|| s.as_bytes())
+                        .map(|bytes| (bytes[0], bytes[1]))
+                        .ok_or(ParseError::TooManyLHSSymbols)
+                })
+        }),
+    )(input)
 }
 
-fn right_hand_side(input: &str) -> IResult<&str, u8> {
-    map_res(alpha1, |r: &str| {
-        r.is_ascii()
-            .then(|| r.as_bytes())
-            .ok_or(ParseError::NonAsciiInput)
-            .and_then(|s| {
-                (s.len() == 1)
-                    .then(|| s.as_bytes().first().copied())
-                    .flatten()
-                    .ok_or(ParseError::WrongNumberOfRHSSymbols)
-            })
-    })(input)
+fn right_hand_side(input: &str) -> IResult<'_, u8> {
+    context(
+        "a single element to insert",
+        map_res(alpha1, |r: &str| {
+            r.is_ascii()
+                .then_some(r.as_bytes())
+                .ok_or(ParseError::NonAsciiInput)
+                .and_then(|s| {
+                    (s.len() == 1)
+                        .then(|| s.as_bytes().first().copied())
+                        .flatten()
+                        .ok_or(ParseError::WrongNumberOfRHSSymbols)
+                })
+        }),
+    )(input)
 }
 
-fn arrow(input: &str) -> IResult<&str, &str> {
-    terminated(preceded(space0, tag("->")), space0)(input)
+fn arrow(input: &str) -> IResult<'_, &str> {
+    context(
+        "`->` between the pair and the inserted element",
+        terminated(preceded(space0, tag("->")), space0),
+    )(input)
 }
 
-fn replacement_rule(input: &str) -> IResult<&str, ((u8, u8), u8)> {
+fn replacement_rule(input: &str) -> IResult<'_, ((u8, u8), u8)> {
     separated_pair(left_hand_side, arrow, right_hand_side)(input)
 }
 
@@ -89,7 +112,52 @@ pub enum ParseError {
 
     #[error("there should be only one symbol on the right hand side")]
     WrongNumberOfRHSSymbols,
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{
+        collection::{hash_map, vec},
+        prelude::*,
+    };
+
+    use super::{parse, Input};
+
+    fn elements() -> impl Strategy<Value = u8> {
+        b'A'..=b'Z'
+    }
+
+    fn inputs() -> impl Strategy<Value = Input> {
+        (
+            vec(elements(), 1..30),
+            hash_map((elements(), elements()), elements(), 1..50),
+        )
+            .prop_map(|(polymer, replacement_rule)| Input {
+                polymer,
+                replacement_rule,
+            })
+    }
+
+    fn render(input: &Input) -> String {
+        let mut text = String::from_utf8(input.polymer.clone()).unwrap();
+        text.push_str("\n\n");
+
+        for (&(l, r), &byte) in &input.replacement_rule {
+            text.push_str(&format!("{}{} -> {}\n", l as char, r as char, byte as char));
+        }
+
+        text
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(input in inputs()) {
+            prop_assert_eq!(&input, &parse(&render(&input)).unwrap());
+        }
 
-    #[error("parse error {0}")]
-    ParseError(nom::error::Error<String>),
+        #[test]
+        fn test_does_not_panic(input in "\\PC*") {
+            let _ = parse(&input);
+        }
+    }
 }
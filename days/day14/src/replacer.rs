@@ -3,14 +3,14 @@ use std::collections::HashMap;
 use crate::parser::Input;
 
 pub struct Replacer<'a> {
-    input: &'a Input<'a>,
+    input: &'a Input,
     frequencies: HashMap<u8, usize>,
     from: HashMap<(u8, u8), usize>,
     to: HashMap<(u8, u8), usize>,
 }
 
 impl<'a> Replacer<'a> {
-    pub fn new(input: &'a Input<'a>) -> Self {
+    pub fn new(input: &'a Input) -> Self {
         let mut from = HashMap::new();
         let mut frequencies = HashMap::new();
 
@@ -18,7 +18,7 @@ impl<'a> Replacer<'a> {
             *from.entry((window[0], window[1])).or_default() += 1;
         }
 
-        for byte in input.polymer {
+        for byte in &input.polymer {
             *frequencies.entry(*byte).or_default() += 1;
         }
 
@@ -0,0 +1,38 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use day15::{astar::a_star, grid::parse_grid, Part1, Part2};
+
+const EXAMPLE: &str = include_str!("../input/test.txt");
+
+/// Tiles the example `factor` times in each direction.
+fn scale(input: &str, factor: usize) -> String {
+    input
+        .lines()
+        .map(|line| line.repeat(factor) + "\n")
+        .collect::<String>()
+        .repeat(factor)
+}
+
+/// Finds the lowest risk path on the example tiled up to 10x10 times, both on the grid as given
+/// (part 1) and on the grid expanded five times over (part 2).
+fn find_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day15/a_star");
+
+    for factor in [1, 10] {
+        let grid = parse_grid(&scale(EXAMPLE, factor)).expect("example must parse");
+        let size = grid.height();
+
+        group.bench_with_input(BenchmarkId::new("part_1", size), &grid, |b, grid| {
+            b.iter(|| black_box(a_star::<Part1>(grid)))
+        });
+        group.bench_with_input(BenchmarkId::new("part_2", size), &grid, |b, grid| {
+            b.iter(|| black_box(a_star::<Part2>(grid)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, find_path);
+criterion_main!(benches);
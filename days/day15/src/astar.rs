@@ -1,3 +1,5 @@
+use grid::Neighborhood;
+
 use crate::{
     grid::{GridRef, Position},
     search::Search,
@@ -7,11 +9,12 @@ pub trait GridStrategy {
     fn cost(position: &Position, grid: GridRef) -> usize;
 
     fn bounds(grid: GridRef) -> Position {
-        Position::bounds(grid)
+        grid.bounds()
     }
 
     fn end(grid: GridRef) -> Position {
-        Self::bounds(grid) - 1
+        let bounds = Self::bounds(grid);
+        Position::new(bounds.x - 1, bounds.y - 1)
     }
 }
 
@@ -26,7 +29,7 @@ pub fn a_star<P: GridStrategy>(grid: GridRef) -> Option<usize> {
             return Some(current_cost);
         }
 
-        for neighbor in current.neighbors(&bounds) {
+        for neighbor in current.neighbors(bounds.x, bounds.y, Neighborhood::Four) {
             search.visit(&neighbor, current_cost + P::cost(&neighbor, grid))
         }
     }
@@ -1,93 +1,21 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-    marker::PhantomData,
-    path::Path,
-};
+use std::{io::Read, marker::PhantomData};
 
-use crate::astar::GridStrategy;
-
-pub type Grid = Vec<Vec<usize>>;
-pub type GridRef<'a> = &'a [Vec<usize>];
-
-pub fn read_grid(path: &Path) -> Result<Grid, std::io::Error> {
-    BufReader::new(File::open(path)?)
-        .lines()
-        .map(|r| {
-            r.map(|line| {
-                line.chars()
-                    .map(|c| c.to_digit(10).expect("characters must be valid digits") as usize)
-                    .collect()
-            })
-        })
-        .collect()
-}
-
-#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
-pub struct Position {
-    x: usize,
-    y: usize,
-}
-
-impl Position {
-    pub const ZERO: Self = Self { x: 0, y: 0 };
-
-    pub fn bounds(grid: GridRef) -> Self {
-        Self {
-            y: grid.len(),
-            x: grid
-                .first()
-                .expect("there must be at least one row in the grid")
-                .len(),
-        }
-    }
-
-    pub fn distance(&self, other: &Self) -> usize {
-        (self.x.max(other.x) - self.x.min(other.x)) + (self.y.max(other.y) - self.y.min(other.y))
-    }
-
-    pub fn lookup(&self, grid: GridRef) -> Option<usize> {
-        grid.get(self.y)
-            .and_then(|r| r.get(self.x).map(|x| *x as usize))
-    }
+use common::Diagnostic;
 
-    pub fn neighbors(&self, bounds: &Self) -> impl Iterator<Item = Position> {
-        std::iter::empty()
-            .chain(self.x.checked_sub(1).map(|x| Position { x, ..*self }))
-            .chain(self.y.checked_sub(1).map(|y| Position { y, ..*self }))
-            .chain(
-                (self.x + 1 < bounds.x)
-                    .then(|| self.x + 1)
-                    .map(|x| Position { x, ..*self }),
-            )
-            .chain(
-                (self.y + 1 < bounds.y)
-                    .then(|| self.y + 1)
-                    .map(|y| Position { y, ..*self }),
-            )
-    }
-}
+use crate::astar::GridStrategy;
 
-impl std::ops::Sub<usize> for Position {
-    type Output = Self;
+pub type Grid = grid::Grid<u8>;
+pub type GridRef<'a> = &'a Grid;
+pub type Position = grid::Point;
 
-    fn sub(self, rhs: usize) -> Self::Output {
-        Self {
-            x: self.x - rhs,
-            y: self.y - rhs,
-        }
-    }
+pub fn read_grid<R: Read>(mut reader: R) -> color_eyre::Result<Grid> {
+    let mut buf = String::new();
+    reader.read_to_string(&mut buf)?;
+    Ok(parse_grid(&buf)?)
 }
 
-impl std::ops::Mul<usize> for Position {
-    type Output = Self;
-
-    fn mul(self, rhs: usize) -> Self::Output {
-        Self {
-            x: self.x * rhs,
-            y: self.y * rhs,
-        }
-    }
+pub fn parse_grid(input: &str) -> Result<Grid, Diagnostic> {
+    Grid::parse_digits(input)
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -95,7 +23,7 @@ pub struct Base;
 
 impl GridStrategy for Base {
     fn cost(position: &Position, grid: GridRef) -> usize {
-        position.lookup(grid).expect("position must be in range")
+        grid[*position] as usize
     }
 }
 
@@ -117,6 +45,21 @@ impl<P: GridStrategy, const M: usize> GridStrategy for Multiplier<P, M> {
     }
 
     fn bounds(grid: GridRef) -> Position {
-        Base::bounds(grid) * M
+        let bounds = Base::bounds(grid);
+        Position::new(bounds.x * M, bounds.y * M)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_grid, read_grid};
+
+    #[test]
+    fn test_read_grid_from_memory() {
+        let input = include_str!("../input/test.txt");
+        let grid = read_grid(input.as_bytes()).expect("reading from a slice can't fail");
+
+        assert_eq!(10, grid.height());
+        assert_eq!(parse_grid(input).unwrap(), grid);
     }
 }
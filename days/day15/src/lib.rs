@@ -0,0 +1,35 @@
+use astar::a_star;
+use color_eyre::eyre::eyre;
+use common::Day;
+
+use crate::grid::{Base, Multiplier};
+
+pub mod astar;
+pub mod generate;
+pub mod grid;
+pub mod search;
+
+pub type Part1 = Base;
+pub type Part2 = Multiplier<Base, 5>;
+
+pub struct Day15;
+
+impl Day for Day15 {
+    const DAY: usize = 15;
+
+    type Input = grid::Grid;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> color_eyre::Result<Self::Input> {
+        Ok(grid::parse_grid(input)?)
+    }
+
+    fn part_1(input: &Self::Input) -> color_eyre::Result<Self::Part1> {
+        a_star::<Part1>(input).ok_or_else(|| eyre!("there is no path through the grid"))
+    }
+
+    fn part_2(input: &Self::Input) -> color_eyre::Result<Self::Part2> {
+        a_star::<Part2>(input).ok_or_else(|| eyre!("there is no path through the grid"))
+    }
+}
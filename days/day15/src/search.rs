@@ -32,9 +32,9 @@ impl Search {
 
     pub fn visit(&mut self, position: &Position, cost: usize) {
         if self.should_update_position(position, cost) {
-            self.costs.insert(position.clone(), cost);
+            self.costs.insert(*position, cost);
             self.queue.push(Node {
-                position: position.clone(),
+                position: *position,
                 priority: cost + self.distance_from_end(position),
             })
         }